@@ -0,0 +1,208 @@
+use crate::vec3::Vec3;
+
+/// An axis aligned bounding box, described by its minimum and maximum corners.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct Aabb {
+    pub minimum: Vec3,
+    pub maximum: Vec3,
+}
+
+impl Aabb {
+    pub fn new(minimum: Vec3, maximum: Vec3) -> Aabb {
+        Aabb { minimum, maximum }
+    }
+
+    /// The point at the center of the box.
+    pub fn centroid(&self) -> Vec3 {
+        0.5 * (self.minimum + self.maximum)
+    }
+
+    /// The total area of the six faces of the box.
+    ///
+    /// Used to estimate how likely a ray is to hit the box, e.g. when deciding where
+    /// to split a bounding volume hierarchy.
+    pub fn surface_area(&self) -> f64 {
+        let extent = self.maximum - self.minimum;
+        2.0 * (extent.x * extent.y + extent.y * extent.z + extent.z * extent.x)
+    }
+
+    /// Index of the axis the box is longest along (0 for x, 1 for y and 2 for z).
+    pub fn longest_axis(&self) -> usize {
+        (self.maximum - self.minimum).max_component_index()
+    }
+
+    /// Copy of the box where no axis is thinner than delta.
+    ///
+    /// Flat objects like rectangles produce boxes with no thickness along one axis, which
+    /// makes ray intersection tests against them unreliable. Any axis thinner than delta is
+    /// grown to exactly delta, keeping it centered on the same point.
+    ///
+    /// * `delta` - The minimum thickness of each axis
+    pub fn pad(&self, delta: f64) -> Aabb {
+        let pad_axis = |min: f64, max: f64| {
+            if max - min >= delta {
+                (min, max)
+            } else {
+                let center = 0.5 * (min + max);
+                (center - 0.5 * delta, center + 0.5 * delta)
+            }
+        };
+
+        let (min_x, max_x) = pad_axis(self.minimum.x, self.maximum.x);
+        let (min_y, max_y) = pad_axis(self.minimum.y, self.maximum.y);
+        let (min_z, max_z) = pad_axis(self.minimum.z, self.maximum.z);
+
+        Aabb::new(
+            Vec3::new(min_x, min_y, min_z),
+            Vec3::new(max_x, max_y, max_z),
+        )
+    }
+
+    /// The overlapping region of this and another box.
+    ///
+    /// Returns None if the boxes don't overlap at all. Boxes which only touch produce
+    /// a box with zero thickness along the touching axis.
+    ///
+    /// * `other` - The other box to intersect with
+    pub fn intersection(&self, other: &Aabb) -> Option<Aabb> {
+        let minimum = Vec3::new(
+            self.minimum.x.max(other.minimum.x),
+            self.minimum.y.max(other.minimum.y),
+            self.minimum.z.max(other.minimum.z),
+        );
+        let maximum = Vec3::new(
+            self.maximum.x.min(other.maximum.x),
+            self.maximum.y.min(other.maximum.y),
+            self.maximum.z.min(other.maximum.z),
+        );
+
+        if minimum.x > maximum.x || minimum.y > maximum.y || minimum.z > maximum.z {
+            None
+        } else {
+            Some(Aabb::new(minimum, maximum))
+        }
+    }
+
+    /// The smallest box surrounding both this and another box.
+    ///
+    /// * `other` - The other box to surround
+    pub fn union(&self, other: &Aabb) -> Aabb {
+        Aabb::new(
+            Vec3::new(
+                self.minimum.x.min(other.minimum.x),
+                self.minimum.y.min(other.minimum.y),
+                self.minimum.z.min(other.minimum.z),
+            ),
+            Vec3::new(
+                self.maximum.x.max(other.maximum.x),
+                self.maximum.y.max(other.maximum.y),
+                self.maximum.z.max(other.maximum.z),
+            ),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn centroid() {
+        let aabb = Aabb::new(Vec3::new(0, 0, 0), Vec3::new(1, 1, 1));
+        let expected = Vec3::new(0.5, 0.5, 0.5);
+
+        assert_eq!(
+            aabb.centroid(),
+            expected,
+            "Aabb.centroid() ({:?}) should match expected ({:?})",
+            aabb.centroid(),
+            expected
+        );
+    }
+
+    #[test]
+    fn surface_area() {
+        let aabb = Aabb::new(Vec3::new(0, 0, 0), Vec3::new(1, 1, 1));
+        let expected = 6.0;
+
+        assert_eq!(
+            aabb.surface_area(),
+            expected,
+            "Aabb.surface_area() ({}) should match expected ({})",
+            aabb.surface_area(),
+            expected
+        );
+    }
+
+    #[test]
+    fn longest_axis() {
+        let aabb = Aabb::new(Vec3::new(0, 0, 0), Vec3::new(1, 3, 2));
+        let expected = 1;
+
+        assert_eq!(
+            aabb.longest_axis(),
+            expected,
+            "Aabb.longest_axis() ({}) should match expected ({})",
+            aabb.longest_axis(),
+            expected
+        );
+    }
+
+    #[test]
+    fn intersection_overlapping() {
+        let aabb = Aabb::new(Vec3::new(0, 0, 0), Vec3::new(2, 2, 2));
+        let other = Aabb::new(Vec3::new(1, -1, 1), Vec3::new(3, 1, 3));
+        let expected = Aabb::new(Vec3::new(1, 0, 1), Vec3::new(2, 1, 2));
+
+        assert_eq!(
+            aabb.intersection(&other),
+            Some(expected),
+            "Aabb.intersection(Other) ({:?}) should match expected ({:?})",
+            aabb.intersection(&other),
+            expected
+        );
+    }
+
+    #[test]
+    fn intersection_disjoint() {
+        let aabb = Aabb::new(Vec3::new(0, 0, 0), Vec3::new(1, 1, 1));
+        let other = Aabb::new(Vec3::new(2, 0, 0), Vec3::new(3, 1, 1));
+
+        assert_eq!(
+            aabb.intersection(&other),
+            None,
+            "Aabb.intersection(Other) ({:?}) should be None for disjoint boxes",
+            aabb.intersection(&other)
+        );
+    }
+
+    #[test]
+    fn union() {
+        let aabb = Aabb::new(Vec3::new(0, 0, 0), Vec3::new(1, 1, 1));
+        let other = Aabb::new(Vec3::new(2, -1, 0), Vec3::new(3, 1, 0.5));
+        let expected = Aabb::new(Vec3::new(0, -1, 0), Vec3::new(3, 1, 1));
+
+        assert_eq!(
+            aabb.union(&other),
+            expected,
+            "Aabb.union(Other) ({:?}) should match expected ({:?})",
+            aabb.union(&other),
+            expected
+        );
+    }
+
+    #[test]
+    fn pad() {
+        let aabb = Aabb::new(Vec3::new(0, 0, 0), Vec3::new(1, 1, 0));
+        let expected = Aabb::new(Vec3::new(0, 0, -0.00005), Vec3::new(1, 1, 0.00005));
+
+        assert_eq!(
+            aabb.pad(0.0001),
+            expected,
+            "Aabb.pad(0.0001) ({:?}) should match expected ({:?})",
+            aabb.pad(0.0001),
+            expected
+        );
+    }
+}
@@ -0,0 +1,178 @@
+use crate::ray::Ray;
+use crate::vec3::Vec3;
+
+/// Something that provides a color for rays which don't hit anything in the scene.
+pub trait Background {
+    /// Returns the color seen along a ray that missed every object
+    ///
+    /// * `ray` - The ray that escaped the scene
+    fn color(&self, ray: &Ray) -> Vec3;
+}
+
+/// A background that is the same color in every direction.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct SolidBackground(pub Vec3);
+
+impl Background for SolidBackground {
+    fn color(&self, _ray: &Ray) -> Vec3 {
+        self.0
+    }
+}
+
+/// A background that blends between two colors based on how far up the ray points.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct GradientBackground {
+    pub top: Vec3,
+    pub bottom: Vec3,
+}
+
+impl Background for GradientBackground {
+    fn color(&self, ray: &Ray) -> Vec3 {
+        let unit_direction = ray.direction.normalized();
+        // Maps the y component from -1..1 into 0..1
+        let t = 0.5 * (unit_direction.y + 1.0);
+
+        // Lerp between the bottom and top colors based on the y component of the normalized vector
+        (1.0 - t) * self.bottom + t * self.top
+    }
+}
+
+/// A sun which appears as a bright disk in the sky.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct Sun {
+    /// The direction from the scene towards the sun, doesn't need to be normalized
+    pub direction: Vec3,
+    /// How bright the sun's disk is
+    pub intensity: f64,
+    /// How large the sun's disk appears, measured in radians from its center to its edge
+    pub angular_radius: f64,
+}
+
+impl Sun {
+    /// Returns if the ray points somewhere within the sun's disk
+    ///
+    /// * `ray` - The ray to check against the sun
+    pub fn is_visible_along(&self, ray: &Ray) -> bool {
+        // The angle between the ray and the sun's direction needs to be within the sun's
+        // radius, which is cheaper to check by comparing the cosines of the angles instead
+        ray.direction.normalized().dot(self.direction.normalized()) >= self.angular_radius.cos()
+    }
+}
+
+/// A gradient sky with an overall brightness and an optional sun.
+///
+/// Gives outdoor scenes a key light without needing to add any light emitting geometry.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct SkyBackground {
+    pub gradient: GradientBackground,
+    /// Multiplier applied to the gradient
+    pub intensity: f64,
+    pub sun: Option<Sun>,
+}
+
+impl Background for SkyBackground {
+    fn color(&self, ray: &Ray) -> Vec3 {
+        let sky = self.intensity * self.gradient.color(ray);
+
+        match self.sun {
+            Some(sun) if sun.is_visible_along(ray) => sky + sun.intensity * Vec3::ONE,
+            _ => sky,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn solid() {
+        let background = SolidBackground(Vec3::new(0.2, 0.4, 0.6));
+        let ray = Ray::new(Vec3::new(0, 0, 0), Vec3::new(1, 2, 3));
+
+        assert_eq!(
+            background.color(&ray),
+            background.0,
+            "SolidBackground.color ({:?}) should match expected ({:?})",
+            background.color(&ray),
+            background.0
+        );
+    }
+
+    #[test]
+    fn gradient() {
+        let background = GradientBackground {
+            top: Vec3::new(0.5, 0.7, 1),
+            bottom: Vec3::new(1, 1, 1),
+        };
+        let up = Ray::new(Vec3::new(0, 0, 0), Vec3::new(0, 1, 0));
+        let down = Ray::new(Vec3::new(0, 0, 0), Vec3::new(0, -1, 0));
+
+        assert_eq!(
+            background.color(&up),
+            background.top,
+            "GradientBackground.color(up) ({:?}) should match top ({:?})",
+            background.color(&up),
+            background.top
+        );
+        assert_eq!(
+            background.color(&down),
+            background.bottom,
+            "GradientBackground.color(down) ({:?}) should match bottom ({:?})",
+            background.color(&down),
+            background.bottom
+        );
+    }
+
+    #[test]
+    fn sky_intensity() {
+        let gradient = GradientBackground {
+            top: Vec3::new(0.5, 0.7, 1),
+            bottom: Vec3::new(1, 1, 1),
+        };
+        let background = SkyBackground {
+            gradient,
+            intensity: 2.0,
+            sun: None,
+        };
+        let ray = Ray::new(Vec3::new(0, 0, 0), Vec3::new(0, 1, 0));
+        let expected = 2.0 * gradient.top;
+
+        assert_eq!(
+            background.color(&ray),
+            expected,
+            "SkyBackground.color ({:?}) should match expected ({:?})",
+            background.color(&ray),
+            expected
+        );
+    }
+
+    #[test]
+    fn sky_sun() {
+        let background = SkyBackground {
+            gradient: GradientBackground {
+                top: Vec3::new(0.5, 0.7, 1),
+                bottom: Vec3::new(1, 1, 1),
+            },
+            intensity: 1.0,
+            sun: Some(Sun {
+                direction: Vec3::new(1, 1, 0),
+                intensity: 50.0,
+                angular_radius: 0.05,
+            }),
+        };
+        let towards_sun = Ray::new(Vec3::new(0, 0, 0), Vec3::new(2, 2, 0));
+        let off_axis = Ray::new(Vec3::new(0, 0, 0), Vec3::new(-1, 1, 0));
+
+        let sun_brightness = background.color(&towards_sun).length();
+        let sky_brightness = background.color(&off_axis).length();
+
+        assert!(
+            sun_brightness > 10.0 * sky_brightness,
+            "Color towards the sun ({:?}) should be much brighter than off axis ({:?})",
+            background.color(&towards_sun),
+            background.color(&off_axis)
+        );
+    }
+}
@@ -1,4 +1,6 @@
+use raytracing_in_one_weekend::background::{Background, GradientBackground};
 use raytracing_in_one_weekend::ray::Ray;
+use raytracing_in_one_weekend::settings::RenderSettings;
 use raytracing_in_one_weekend::vec3::Vec3;
 
 type Color = Vec3;
@@ -9,8 +11,13 @@ fn main() {
 
     // Ensures that the final image height matches the aspect ratio to prevent
     // a stretched image.
-    let width = 400;
-    let height = (width as f64 / aspect_ratio) as i32;
+    let settings = RenderSettings {
+        image_width: 400,
+        aspect_ratio,
+        ..RenderSettings::default()
+    };
+    let width = settings.image_width;
+    let height = settings.image_height();
 
     // The viewport will be between -1 and 1 on the vertical axis,
     // with a normalized coordinate system scaled to the aspect ratio
@@ -27,6 +34,12 @@ fn main() {
     // the origin will get us the bottom left, then we shift by the focal length.
     let lower_left_corner = origin - horizontal / 2 - vertical / 2 - Vec3::new(0, 0, focal_length);
 
+    // Blends from white at the bottom of the image to blue at the top
+    let background = GradientBackground {
+        top: Color::new(0.5, 0.7, 1),
+        bottom: Color::new(1, 1, 1),
+    };
+
     // We write the output to the stdout so a terminal user can redirect into a file or another
     // process.
 
@@ -44,20 +57,21 @@ fn main() {
             // As we go along through the rendered image, we increment the u and v coordinates
             // to correspond to the location we would be in the final texture (if we were to 
             // render to a texture or frame buffer directly)
-            let u = (column as f64) / ((width - 1) as f64);
-
             // Since the row iterator starts reversed, it means that we'll start at the top
             // of the image instead of the bottom when rendering.
-            let v = (row as f64) / ((height - 1) as f64);
+            let (u, v) = settings.pixel_uv(column, row);
 
             // Shoot a ray from the location of the camera (0, 0, 0)
             // moving from the top left to the bottom right of the image as time goes on
-            let color: Color = ray_color(Ray::new(
-                origin,
-                lower_left_corner + u * horizontal + v * vertical - origin,
-            ));
-
-            write_color(color);
+            let color: Color = ray_color(
+                Ray::new(
+                    origin,
+                    lower_left_corner + u * horizontal + v * vertical - origin,
+                ),
+                &background,
+            );
+
+            write_color(color, &settings);
         }
     }
 
@@ -65,20 +79,12 @@ fn main() {
 }
 
 /// Given a ray calculates a color to represent either the background
-fn ray_color(ray: Ray) -> Color {
-    let unit_direction = ray.direction.normalized();
-    let t = 0.5 * (unit_direction.y + 1.0);
-
-    // Lerp between white and blue based on the y component of the normalized vector
-    (1.0 - t) * Color::new(1, 1, 1) + t * Color::new(0.5, 0.7, 1)
+fn ray_color(ray: Ray, background: &dyn Background) -> Color {
+    background.color(&ray)
 }
 
-fn write_color(color: Color) {
-    // Convert the color from 0-1 to 0-255
-    let (ir, ig, ib) = (
-        (color[0] * 255.99) as u8,
-        (color[1] * 255.99) as u8,
-        (color[2] * 255.99) as u8,
-    );
+fn write_color(color: Color, settings: &RenderSettings) {
+    // Exposure and white balance are applied in linear space, before clamping
+    let [ir, ig, ib] = settings.color_grade(color).to_rgb8(1, false);
     println!("{} {} {}", ir, ig, ib);
 }
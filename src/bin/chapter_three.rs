@@ -39,11 +39,6 @@ fn main() {
 }
 
 fn write_color(color: Color) {
-    // Convert the color from 0-1 to 0-255
-    let (ir, ig, ib) = (
-        (color[0] * 255.99) as u8,
-        (color[1] * 255.99) as u8,
-        (color[2] * 255.99) as u8,
-    );
+    let [ir, ig, ib] = color.to_rgb8(1, false);
     println!("{} {} {}", ir, ig, ib);
 }
@@ -1,3 +1,5 @@
+use raytracing_in_one_weekend::vec3::Vec3;
+
 fn main() {
     let (width, height) = (256, 256);
 
@@ -26,7 +28,7 @@ fn main() {
             let b = 0.25;
 
             // Convert the pixels from 0-1 to 0-255
-            let (ir, ig, ib) = ((r * 255.99) as u8, (g * 255.99) as u8, (b * 255.99) as u8);
+            let [ir, ig, ib] = Vec3::new(r, g, b).to_rgb8(1, false);
 
             println!("{} {} {}", ir, ig, ib);
         }
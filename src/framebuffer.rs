@@ -0,0 +1,182 @@
+use crate::vec3::Vec3;
+
+/// A rendered image held in memory, as linear colors.
+///
+/// Pixels are stored row by row starting from the top left, the same order they're written
+/// out to an image file.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Framebuffer {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<Vec3>,
+}
+
+impl Framebuffer {
+    /// Creates a framebuffer with every pixel set to black.
+    ///
+    /// * `width` - The width of the image in pixels
+    /// * `height` - The height of the image in pixels
+    pub fn new(width: u32, height: u32) -> Framebuffer {
+        Framebuffer {
+            width,
+            height,
+            pixels: vec![Vec3::ZERO; (width * height) as usize],
+        }
+    }
+
+    /// Returns the color of a pixel.
+    ///
+    /// * `column` - The pixel's column, counting from the left
+    /// * `row` - The pixel's row, counting from the top
+    pub fn get(&self, column: u32, row: u32) -> Vec3 {
+        self.pixels[self.index(column, row)]
+    }
+
+    /// Sets the color of a pixel.
+    ///
+    /// * `column` - The pixel's column, counting from the left
+    /// * `row` - The pixel's row, counting from the top
+    /// * `color` - The linear color to store
+    pub fn set(&mut self, column: u32, row: u32, color: Vec3) {
+        let index = self.index(column, row);
+        self.pixels[index] = color;
+    }
+
+    /// Replaces any pixel with a NaN or infinite component with black.
+    ///
+    /// Pathological paths can still produce these despite all the guards, and they'd
+    /// otherwise come out as black or wrapped values depending on the output format.
+    /// Returns the number of pixels that were replaced, so the caller can report it.
+    pub fn sanitize(&mut self) -> usize {
+        let mut replaced = 0;
+
+        for pixel in self.pixels.iter_mut() {
+            if !pixel.into_iter().all(f64::is_finite) {
+                *pixel = Vec3::ZERO;
+                replaced += 1;
+            }
+        }
+
+        replaced
+    }
+
+    /// Converts every pixel into RGBA f32 values, ready to upload into a GPU buffer.
+    pub fn as_rgba_f32(&self) -> Vec<[f32; 4]> {
+        self.pixels.iter().map(|&pixel| pixel.into()).collect()
+    }
+
+    fn index(&self, column: u32, row: u32) -> usize {
+        assert!(
+            column < self.width && row < self.height,
+            "Pixel ({}, {}) is outside of the {}x{} framebuffer",
+            column,
+            row,
+            self.width,
+            self.height
+        );
+
+        (row * self.width + column) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn new_framebuffer() {
+        let framebuffer = Framebuffer::new(4, 3);
+
+        assert_eq!(
+            framebuffer.pixels.len(),
+            12,
+            "Framebuffer.pixels.len() ({}) should match expected ({})",
+            framebuffer.pixels.len(),
+            12
+        );
+        assert!(
+            framebuffer.pixels.iter().all(|&p| p == Vec3::ZERO),
+            "Every pixel of a new framebuffer ({:?}) should be black",
+            framebuffer.pixels
+        );
+    }
+
+    #[test]
+    fn get_set() {
+        let mut framebuffer = Framebuffer::new(4, 3);
+        let color = Vec3::new(0.1, 0.2, 0.3);
+        framebuffer.set(3, 1, color);
+
+        assert_eq!(
+            framebuffer.get(3, 1),
+            color,
+            "Framebuffer.get(3, 1) ({:?}) should match expected ({:?})",
+            framebuffer.get(3, 1),
+            color
+        );
+        assert_eq!(
+            framebuffer.pixels[7], color,
+            "Pixel (3, 1) ({:?}) should be stored row by row ({:?})",
+            framebuffer.pixels[7], color
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Pixel (4, 0) is outside of the 4x3 framebuffer")]
+    fn get_outside() {
+        let framebuffer = Framebuffer::new(4, 3);
+        framebuffer.get(4, 0);
+    }
+
+    #[test]
+    fn sanitize() {
+        let mut framebuffer = Framebuffer::new(4, 3);
+        let color = Vec3::new(0.1, 0.2, 0.3);
+        framebuffer.set(0, 0, color);
+        framebuffer.set(2, 1, Vec3::new(0.5, f64::NAN, 0.5));
+
+        let replaced = framebuffer.sanitize();
+
+        assert_eq!(
+            replaced, 1,
+            "Framebuffer.sanitize() ({}) should replace a single pixel",
+            replaced
+        );
+        assert_eq!(
+            framebuffer.get(2, 1),
+            Vec3::ZERO,
+            "The NaN pixel ({:?}) should be replaced with black",
+            framebuffer.get(2, 1)
+        );
+        assert_eq!(
+            framebuffer.get(0, 0),
+            color,
+            "Finite pixels ({:?}) should be left unchanged ({:?})",
+            framebuffer.get(0, 0),
+            color
+        );
+    }
+
+    #[test]
+    fn as_rgba_f32() {
+        let mut framebuffer = Framebuffer::new(4, 3);
+        framebuffer.set(1, 0, Vec3::new(0.5, 0.25, 0.75));
+        let rgba = framebuffer.as_rgba_f32();
+
+        assert_eq!(
+            rgba.len(),
+            framebuffer.pixels.len(),
+            "Framebuffer.as_rgba_f32().len() ({}) should match the pixel count ({})",
+            rgba.len(),
+            framebuffer.pixels.len()
+        );
+        assert_eq!(
+            rgba[1],
+            [0.5, 0.25, 0.75, 1.0],
+            "Framebuffer.as_rgba_f32()[1] ({:?}) should match expected ({:?})",
+            rgba[1],
+            [0.5, 0.25, 0.75, 1.0]
+        );
+    }
+}
@@ -1,2 +1,12 @@
+pub mod aabb;
+pub mod background;
+pub mod framebuffer;
+pub mod matrix;
+pub mod onb;
+pub mod ppm;
+pub mod ray;
+pub mod settings;
+pub mod sphere;
+pub mod texture;
+pub mod tiles;
 pub mod vec3;
-pub mod ray;
\ No newline at end of file
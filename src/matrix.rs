@@ -0,0 +1,130 @@
+use crate::vec3::Vec3;
+
+/// A 4x4 matrix for transforming points and vectors, stored as rows.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct Matrix4 {
+    pub rows: [[f64; 4]; 4],
+}
+
+impl Matrix4 {
+    /// The matrix which leaves everything unchanged.
+    pub const IDENTITY: Matrix4 = Matrix4 {
+        rows: [
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ],
+    };
+
+    /// A matrix which moves points by an offset.
+    ///
+    /// * `offset` - How far to move along each axis
+    pub fn translation(offset: Vec3) -> Matrix4 {
+        Matrix4 {
+            rows: [
+                [1.0, 0.0, 0.0, offset.x],
+                [0.0, 1.0, 0.0, offset.y],
+                [0.0, 0.0, 1.0, offset.z],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+        }
+    }
+
+    /// A matrix which rotates around the y axis.
+    ///
+    /// Positive angles rotate the z axis towards the x axis.
+    ///
+    /// * `radians` - The angle to rotate by
+    pub fn rotation_y(radians: f64) -> Matrix4 {
+        let (sin_theta, cos_theta) = radians.sin_cos();
+
+        Matrix4 {
+            rows: [
+                [cos_theta, 0.0, sin_theta, 0.0],
+                [0.0, 1.0, 0.0, 0.0],
+                [-sin_theta, 0.0, cos_theta, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+        }
+    }
+
+    /// Transforms a position, which is affected by translation.
+    ///
+    /// * `p` - The point to transform
+    pub fn transform_point(&self, p: Vec3) -> Vec3 {
+        self.transform(p, 1.0)
+    }
+
+    /// Transforms a direction, which isn't affected by translation.
+    ///
+    /// * `v` - The vector to transform
+    pub fn transform_vector(&self, v: Vec3) -> Vec3 {
+        self.transform(v, 0.0)
+    }
+
+    // Multiplies the matrix by (x, y, z, w), where w is 1 for points and 0 for vectors so
+    // vectors ignore the translation column
+    fn transform(&self, a: Vec3, w: f64) -> Vec3 {
+        let row = |r: [f64; 4]| r[0] * a.x + r[1] * a.y + r[2] * a.z + r[3] * w;
+
+        Vec3::new(row(self.rows[0]), row(self.rows[1]), row(self.rows[2]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const EPSILON: f64 = 1e-10;
+
+    #[test]
+    fn identity() {
+        let p = Vec3::new(1, 2, 3);
+
+        assert_eq!(
+            Matrix4::IDENTITY.transform_point(p),
+            p,
+            "Matrix4::IDENTITY.transform_point ({:?}) should match expected ({:?})",
+            Matrix4::IDENTITY.transform_point(p),
+            p
+        );
+    }
+
+    #[test]
+    fn translation() {
+        let m = Matrix4::translation(Vec3::new(1, 2, 3));
+        let v = Vec3::new(1, 1, 1);
+        let expected = Vec3::new(2, 3, 4);
+
+        assert_eq!(
+            m.transform_point(v),
+            expected,
+            "Translated point ({:?}) should match expected ({:?})",
+            m.transform_point(v),
+            expected
+        );
+        assert_eq!(
+            m.transform_vector(v),
+            v,
+            "Translated vector ({:?}) should be unchanged ({:?})",
+            m.transform_vector(v),
+            v
+        );
+    }
+
+    #[test]
+    fn rotation_y() {
+        let m = Matrix4::rotation_y(std::f64::consts::FRAC_PI_2);
+        let rotated = m.transform_vector(Vec3::new(0, 0, 1));
+        let expected = Vec3::new(1, 0, 0);
+
+        assert!(
+            (rotated - expected).length() < EPSILON,
+            "Rotated vector ({:?}) should match expected ({:?})",
+            rotated,
+            expected
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use crate::vec3::Vec3;
+
+/// An orthonormal basis, three mutually perpendicular unit vectors.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct Onb {
+    pub u: Vec3,
+    pub v: Vec3,
+    pub w: Vec3,
+}
+
+impl Onb {
+    /// Builds an orthonormal basis around a vector.
+    ///
+    /// The w axis points along the given vector, with u and v chosen to be perpendicular
+    /// to it (and to each other), which is handy for working relative to a surface normal.
+    ///
+    /// * `n` - The vector to align the w axis with, doesn't need to be normalized
+    pub fn build_from_w(n: Vec3) -> Onb {
+        let w = n.normalized();
+
+        // Any vector that isn't parallel to w will do for the cross product. The x axis
+        // is used unless w points mostly along x, in which case the y axis is used instead
+        let a = if w.x.abs() > 0.9 {
+            Vec3::new(0, 1, 0)
+        } else {
+            Vec3::new(1, 0, 0)
+        };
+
+        let v = w.cross(a).normalized();
+        let u = w.cross(v);
+
+        Onb { u, v, w }
+    }
+
+    /// Transforms a vector from the local space of this basis into world space.
+    ///
+    /// * `a` - The vector in local coordinates, where x, y and z are along u, v and w
+    pub fn local(&self, a: Vec3) -> Vec3 {
+        a.x * self.u + a.y * self.v + a.z * self.w
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const EPSILON: f64 = 1e-10;
+
+    #[test]
+    fn orthonormal() {
+        for n in [Vec3::new(0, 1, 0), Vec3::new(1, 0, 0), Vec3::new(3, -2, 5)].iter() {
+            let onb = Onb::build_from_w(*n);
+
+            for axis in [onb.u, onb.v, onb.w].iter() {
+                assert!(
+                    (axis.length() - 1.0).abs() < EPSILON,
+                    "Axis ({:?}) should be unit length",
+                    axis
+                );
+            }
+
+            assert!(
+                onb.u.dot(onb.v).abs() < EPSILON,
+                "U ({:?}) should be orthogonal to V ({:?})",
+                onb.u,
+                onb.v
+            );
+            assert!(
+                onb.v.dot(onb.w).abs() < EPSILON,
+                "V ({:?}) should be orthogonal to W ({:?})",
+                onb.v,
+                onb.w
+            );
+            assert!(
+                onb.w.dot(onb.u).abs() < EPSILON,
+                "W ({:?}) should be orthogonal to U ({:?})",
+                onb.w,
+                onb.u
+            );
+            assert!(
+                (onb.w.dot(n.normalized()) - 1.0).abs() < EPSILON,
+                "W ({:?}) should align with N ({:?})",
+                onb.w,
+                n
+            );
+        }
+    }
+
+    #[test]
+    fn local() {
+        let onb = Onb::build_from_w(Vec3::new(0, 0, 2));
+        let transformed = onb.local(Vec3::new(0, 0, 1));
+        let expected = onb.w;
+
+        assert_eq!(
+            transformed, expected,
+            "Onb.local((0, 0, 1)) ({:?}) should match W ({:?})",
+            transformed, expected
+        );
+    }
+}
@@ -0,0 +1,305 @@
+use std::io::{self, Read, Write};
+
+use crate::framebuffer::Framebuffer;
+use crate::vec3::Vec3;
+
+/// The two flavours of PPM image file.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum PpmFormat {
+    /// P3, where every value is written out as text
+    Ascii,
+    /// P6, where every value is written out as raw bytes
+    Binary,
+}
+
+/// Writes a framebuffer out as a PPM image with a maxval of 255.
+///
+/// Colors are clamped to 0-1 and written as is, without any gamma correction.
+///
+/// * `writer` - Where to write the image to
+/// * `framebuffer` - The image to write
+/// * `format` - Whether to write a text (P3) or binary (P6) file
+pub fn write_ppm<W: Write>(
+    writer: &mut W,
+    framebuffer: &Framebuffer,
+    format: PpmFormat,
+) -> io::Result<()> {
+    let magic = match format {
+        PpmFormat::Ascii => "P3",
+        PpmFormat::Binary => "P6",
+    };
+    write!(
+        writer,
+        "{}\n{} {}\n255\n",
+        magic, framebuffer.width, framebuffer.height
+    )?;
+
+    for pixel in framebuffer.pixels.iter() {
+        let [r, g, b] = pixel.to_rgb8(1, false);
+
+        match format {
+            PpmFormat::Ascii => writeln!(writer, "{} {} {}", r, g, b)?,
+            PpmFormat::Binary => writer.write_all(&[r, g, b])?,
+        }
+    }
+
+    Ok(())
+}
+
+/// Reads a PPM image into a framebuffer.
+///
+/// Handles both text (P3) and binary (P6) files, including comments starting with #
+/// anywhere in the header. Values are scaled by the file's maxval into colors between 0 and
+/// 1, so files from other tools with a maxval other than 255 (e.g. 65535 for 16-bit images)
+/// can be read too.
+///
+/// * `reader` - Where to read the image from
+pub fn read_ppm<R: Read>(mut reader: R) -> io::Result<Framebuffer> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+    let mut parser = Parser { bytes, position: 0 };
+
+    let format = match parser.token()?.as_str() {
+        "P3" => PpmFormat::Ascii,
+        "P6" => PpmFormat::Binary,
+        magic => return Err(invalid_data(format!("unknown PPM format '{}'", magic))),
+    };
+    let width = parser.number()?;
+    let height = parser.number()?;
+    let maxval = parser.number()?;
+
+    if maxval == 0 || maxval > 65535 {
+        return Err(invalid_data(format!(
+            "maxval must be between 1 and 65535 but was {}",
+            maxval
+        )));
+    }
+
+    // A single whitespace character separates the header from binary pixel data
+    if format == PpmFormat::Binary {
+        parser.position += 1;
+    }
+
+    let mut framebuffer = Framebuffer::new(width, height);
+
+    for pixel in framebuffer.pixels.iter_mut() {
+        let mut sample = || match format {
+            PpmFormat::Ascii => parser.number(),
+            // Binary values take up two bytes (most significant first) if they can be
+            // larger than 255
+            PpmFormat::Binary if maxval > 255 => {
+                Ok(u32::from(parser.byte()?) << 8 | u32::from(parser.byte()?))
+            }
+            PpmFormat::Binary => parser.byte().map(u32::from),
+        };
+        let (r, g, b) = (sample()?, sample()?, sample()?);
+
+        if r > maxval || g > maxval || b > maxval {
+            return Err(invalid_data(format!(
+                "pixel value ({}, {}, {}) is larger than the maxval of {}",
+                r, g, b, maxval
+            )));
+        }
+
+        *pixel = Vec3::new(r, g, b) / maxval;
+    }
+
+    Ok(framebuffer)
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+// Steps through the contents of a PPM file, which mixes whitespace separated text with
+// (for P6 files) raw bytes
+struct Parser {
+    bytes: Vec<u8>,
+    position: usize,
+}
+
+impl Parser {
+    fn skip_whitespace_and_comments(&mut self) {
+        while let Some(&byte) = self.bytes.get(self.position) {
+            if byte == b'#' {
+                // Comments run until the end of the line
+                while self.bytes.get(self.position).is_some_and(|&b| b != b'\n') {
+                    self.position += 1;
+                }
+            } else if byte.is_ascii_whitespace() {
+                self.position += 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn token(&mut self) -> io::Result<String> {
+        self.skip_whitespace_and_comments();
+        let start = self.position;
+
+        while self
+            .bytes
+            .get(self.position)
+            .is_some_and(|b| !b.is_ascii_whitespace() && *b != b'#')
+        {
+            self.position += 1;
+        }
+
+        if start == self.position {
+            return Err(invalid_data("unexpected end of PPM file".to_string()));
+        }
+
+        Ok(String::from_utf8_lossy(&self.bytes[start..self.position]).into_owned())
+    }
+
+    fn number(&mut self) -> io::Result<u32> {
+        let token = self.token()?;
+        token
+            .parse()
+            .map_err(|_| invalid_data(format!("'{}' is not a valid PPM value", token)))
+    }
+
+    fn byte(&mut self) -> io::Result<u8> {
+        let byte = self
+            .bytes
+            .get(self.position)
+            .copied()
+            .ok_or_else(|| invalid_data("unexpected end of PPM file".to_string()))?;
+        self.position += 1;
+        Ok(byte)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    // Only uses colors which are exactly representable in 8 bits, so they survive being
+    // written out and read back in unchanged
+    fn test_framebuffer() -> Framebuffer {
+        let mut framebuffer = Framebuffer::new(3, 2);
+        framebuffer.set(0, 0, Vec3::from_rgb8([255, 0, 0]));
+        framebuffer.set(1, 0, Vec3::from_rgb8([0, 255, 0]));
+        framebuffer.set(2, 0, Vec3::from_rgb8([0, 0, 255]));
+        framebuffer.set(0, 1, Vec3::from_rgb8([12, 34, 56]));
+        framebuffer.set(1, 1, Vec3::from_rgb8([128, 128, 128]));
+        framebuffer
+    }
+
+    #[test]
+    fn write_ppm_ascii() {
+        let mut framebuffer = Framebuffer::new(2, 1);
+        framebuffer.set(0, 0, Vec3::new(1, 0.5, 0));
+        let mut output = Vec::new();
+        write_ppm(&mut output, &framebuffer, PpmFormat::Ascii).unwrap();
+
+        let expected = "P3\n2 1\n255\n255 127 0\n0 0 0\n";
+
+        assert_eq!(
+            String::from_utf8_lossy(&output),
+            expected,
+            "write_ppm ({:?}) should match expected ({:?})",
+            String::from_utf8_lossy(&output),
+            expected
+        );
+    }
+
+    #[test]
+    fn round_trip_ascii() {
+        let framebuffer = test_framebuffer();
+        let mut output = Vec::new();
+        write_ppm(&mut output, &framebuffer, PpmFormat::Ascii).unwrap();
+        let read = read_ppm(output.as_slice()).unwrap();
+
+        assert_eq!(
+            read, framebuffer,
+            "The P3 file read back in ({:?}) should match the original ({:?})",
+            read, framebuffer
+        );
+    }
+
+    #[test]
+    fn round_trip_binary() {
+        let framebuffer = test_framebuffer();
+        let mut output = Vec::new();
+        write_ppm(&mut output, &framebuffer, PpmFormat::Binary).unwrap();
+        let read = read_ppm(output.as_slice()).unwrap();
+
+        assert_eq!(
+            read, framebuffer,
+            "The P6 file read back in ({:?}) should match the original ({:?})",
+            read, framebuffer
+        );
+    }
+
+    #[test]
+    fn read_ppm_header_comment() {
+        let file = "P3\n# Written by hand\n1 1\n255\n255 0 255\n";
+        let read = read_ppm(file.as_bytes()).unwrap();
+        let expected = Vec3::new(1, 0, 1);
+
+        assert_eq!(
+            read.get(0, 0),
+            expected,
+            "read_ppm ({:?}) should skip the comment and match expected ({:?})",
+            read.get(0, 0),
+            expected
+        );
+    }
+
+    #[test]
+    fn read_ppm_embedded_comments() {
+        let file = concat!(
+            "P3 # a comment after the magic\n",
+            "2 # width\n",
+            "1\n",
+            "# maxval is next\n",
+            "15\n",
+            "15 0 3 # red\n",
+            "0 15 0\n"
+        );
+        let read = read_ppm(file.as_bytes()).unwrap();
+        let expected = [Vec3::new(1, 0, 0.2), Vec3::new(0, 1, 0)];
+
+        assert_eq!(
+            read.pixels, expected,
+            "read_ppm ({:?}) should skip every comment and match expected ({:?})",
+            read.pixels, expected
+        );
+    }
+
+    #[test]
+    fn read_ppm_16_bit() {
+        let mut file = b"P6\n2 1\n65535\n".to_vec();
+        file.extend_from_slice(&[0xff, 0xff, 0x00, 0x00, 0x80, 0x00]);
+        file.extend_from_slice(&[0x00, 0x00, 0xff, 0xff, 0x00, 0x00]);
+        let read = read_ppm(file.as_slice()).unwrap();
+        let expected = [Vec3::new(1, 0, 32768.0 / 65535.0), Vec3::new(0, 1, 0)];
+
+        assert_eq!(
+            read.pixels, expected,
+            "read_ppm ({:?}) should scale 16-bit values by 1/65535 ({:?})",
+            read.pixels, expected
+        );
+    }
+
+    #[test]
+    fn read_ppm_invalid() {
+        for file in [
+            "P5\n1 1\n255\n0",
+            "P3\n1 1\n255\n0 0",
+            "P3\n1 1\n255\n0 0 256",
+        ]
+        .iter()
+        {
+            assert_eq!(
+                read_ppm(file.as_bytes()).map_err(|e| e.kind()),
+                Err(io::ErrorKind::InvalidData),
+                "read_ppm({:?}) should be rejected as invalid data",
+                file
+            );
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use crate::matrix::Matrix4;
 use crate::vec3::Vec3;
 #[derive(Debug, PartialEq, Copy, Clone)]
 pub struct Ray {
@@ -19,6 +20,19 @@ impl Ray {
     pub fn at(&self, t: f64) -> Vec3 {
         self.origin + (self.direction * t)
     }
+
+    /// Returns the ray after being transformed by a matrix
+    ///
+    /// The origin is transformed as a point and the direction as a vector, so only the
+    /// origin is affected by any translation
+    ///
+    /// * `m` - The matrix to transform the ray by
+    pub fn transform_by(&self, m: &Matrix4) -> Ray {
+        Ray::new(
+            m.transform_point(self.origin),
+            m.transform_vector(self.direction),
+        )
+    }
 }
 
 #[cfg(test)]
@@ -42,4 +56,37 @@ mod tests {
             expected
         );
     }
+
+    #[test]
+    fn transform_by_translation() {
+        let ray = Ray::new(Vec3::new(2, 3, 4), Vec3::new(0, 1, 0));
+        let m = Matrix4::translation(Vec3::new(1, 0, -1));
+
+        let expected = Ray::new(Vec3::new(3, 3, 3), Vec3::new(0, 1, 0));
+
+        assert_eq!(
+            ray.transform_by(&m),
+            expected,
+            "Ray.transform_by(translation) ({:?}) should match expected ({:?})",
+            ray.transform_by(&m),
+            expected
+        );
+    }
+
+    #[test]
+    fn transform_by_rotation() {
+        let ray = Ray::new(Vec3::new(0, 0, 2), Vec3::new(0, 0, 1));
+        let m = Matrix4::rotation_y(std::f64::consts::FRAC_PI_2);
+
+        let transformed = ray.transform_by(&m);
+        let expected = Ray::new(Vec3::new(2, 0, 0), Vec3::new(1, 0, 0));
+
+        assert!(
+            (transformed.origin - expected.origin).length() < 1e-10
+                && (transformed.direction - expected.direction).length() < 1e-10,
+            "Ray.transform_by(rotation) ({:?}) should match expected ({:?})",
+            transformed,
+            expected
+        );
+    }
 }
@@ -0,0 +1,726 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::vec3::Vec3;
+
+/// The settings controlling the size and quality of a render.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct RenderSettings {
+    pub image_width: u32,
+    pub aspect_ratio: f64,
+    pub samples_per_pixel: u32,
+    pub max_depth: u32,
+    pub seed: Option<u64>,
+    /// Multiplier applied to every color in linear space before it's written out
+    pub exposure: f64,
+    /// Per channel gain applied to every color in linear space before it's written out
+    pub white_balance: Vec3,
+    /// The brightest an indirect sample is allowed to be, or None to leave samples unclamped
+    pub clamp_indirect: Option<f64>,
+    /// An exact image height to use instead of deriving it from the width and aspect ratio
+    pub image_height_override: Option<u32>,
+    /// How far along a bounced ray to start looking for hits, so it doesn't hit the surface
+    /// it just left due to rounding error ("shadow acne")
+    pub shadow_ray_epsilon: f64,
+}
+
+impl RenderSettings {
+    /// Height of the image in pixels.
+    ///
+    /// Derived from the width and aspect ratio so the final image isn't stretched, and never
+    /// less than a single pixel, even for tiny widths or extreme aspect ratios.
+    ///
+    /// If image_height_override is set it's used as is instead.
+    pub fn image_height(&self) -> u32 {
+        match self.image_height_override {
+            Some(height) => height,
+            None => ((self.image_width as f64 / self.aspect_ratio) as u32).max(1),
+        }
+    }
+
+    /// Renders at an exact resolution instead of deriving the height from the aspect ratio.
+    ///
+    /// The aspect ratio is updated to match, so the camera isn't stretched either.
+    ///
+    /// * `resolution` - The width and height of the image in pixels
+    pub fn set_resolution(&mut self, resolution: Resolution) {
+        self.image_width = resolution.width;
+        self.image_height_override = Some(resolution.height);
+        self.aspect_ratio = resolution.aspect_ratio();
+    }
+
+    /// Converts a pixel's column and row into u and v coordinates between 0 and 1.
+    ///
+    /// The first column or row maps to 0 and the last to 1. A single column or row image
+    /// maps to 0 instead of dividing by zero.
+    ///
+    /// * `column` - The pixel's column, counting from the left
+    /// * `row` - The pixel's row, counting from the bottom
+    pub fn pixel_uv(&self, column: u32, row: u32) -> (f64, f64) {
+        let u = column as f64 / (self.image_width.max(2) - 1) as f64;
+        let v = row as f64 / (self.image_height().max(2) - 1) as f64;
+        (u, v)
+    }
+
+    /// Checks the settings describe an image that can actually be rendered.
+    pub fn validate(&self) -> Result<(), SettingsError> {
+        if self.image_width == 0 {
+            return Err(SettingsError::ZeroWidth);
+        }
+
+        if self.image_height_override == Some(0) {
+            return Err(SettingsError::ZeroHeight);
+        }
+
+        if !self.aspect_ratio.is_finite() || self.aspect_ratio <= 0.0 {
+            return Err(SettingsError::InvalidAspectRatio(self.aspect_ratio));
+        }
+
+        if self.samples_per_pixel == 0 {
+            return Err(SettingsError::ZeroSamples);
+        }
+
+        if let Some(max) = self.clamp_indirect {
+            if !max.is_finite() || max <= 0.0 {
+                return Err(SettingsError::InvalidClampIndirect(max));
+            }
+        }
+
+        if !self.exposure.is_finite() || self.exposure < 0.0 {
+            return Err(SettingsError::InvalidExposure(self.exposure));
+        }
+
+        if self
+            .white_balance
+            .into_iter()
+            .any(|gain| !gain.is_finite() || gain < 0.0)
+        {
+            return Err(SettingsError::InvalidWhiteBalance(self.white_balance));
+        }
+
+        if !self.shadow_ray_epsilon.is_finite() || self.shadow_ray_epsilon <= 0.0 {
+            return Err(SettingsError::InvalidShadowRayEpsilon(
+                self.shadow_ray_epsilon,
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Applies the exposure and white balance to a linear color.
+    ///
+    /// This happens before any gamma correction or clamping, so bright colors can still be
+    /// brought back into range by lowering the exposure.
+    ///
+    /// * `color` - The linear color to adjust
+    pub fn color_grade(&self, color: Vec3) -> Vec3 {
+        color * self.white_balance * self.exposure
+    }
+
+    /// Limits how bright a single indirect sample can be.
+    ///
+    /// Rare paths can return huge values which show up as bright speckles ("fireflies").
+    /// Scaling down any sample brighter than clamp_indirect trades a little bias for a lot
+    /// less noise. The sample is scaled rather than clamped per channel to keep its hue.
+    ///
+    /// * `sample` - The radiance returned by a single indirect sample
+    pub fn clamp_sample(&self, sample: Vec3) -> Vec3 {
+        match self.clamp_indirect {
+            Some(max) if sample.max_component() > max => sample * (max / sample.max_component()),
+            _ => sample,
+        }
+    }
+}
+
+impl Default for RenderSettings {
+    fn default() -> Self {
+        RenderSettings {
+            image_width: 400,
+            aspect_ratio: 16.0 / 9.0,
+            samples_per_pixel: 100,
+            max_depth: 50,
+            seed: None,
+            exposure: 1.0,
+            white_balance: Vec3::ONE,
+            clamp_indirect: None,
+            image_height_override: None,
+            shadow_ray_epsilon: 0.001,
+        }
+    }
+}
+
+/// Error returned when RenderSettings can't be used to render an image.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum SettingsError {
+    /// The image width was zero
+    ZeroWidth,
+    /// The image height override was zero
+    ZeroHeight,
+    /// The aspect ratio wasn't a positive number, holds the offending ratio
+    InvalidAspectRatio(f64),
+    /// The number of samples per pixel was zero
+    ZeroSamples,
+    /// The indirect sample clamp wasn't a positive number, holds the offending clamp
+    InvalidClampIndirect(f64),
+    /// The exposure was negative or not a number, holds the offending exposure
+    InvalidExposure(f64),
+    /// One of the white balance gains was negative or not a number, holds the offending gains
+    InvalidWhiteBalance(Vec3),
+    /// The shadow ray epsilon wasn't a positive number, holds the offending epsilon
+    InvalidShadowRayEpsilon(f64),
+}
+
+impl fmt::Display for SettingsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SettingsError::ZeroWidth => write!(f, "image width must be at least 1 pixel"),
+            SettingsError::ZeroHeight => write!(f, "image height must be at least 1 pixel"),
+            SettingsError::InvalidAspectRatio(ratio) => {
+                write!(
+                    f,
+                    "aspect ratio must be a positive number but was {}",
+                    ratio
+                )
+            }
+            SettingsError::ZeroSamples => write!(f, "samples per pixel must be at least 1"),
+            SettingsError::InvalidClampIndirect(max) => {
+                write!(
+                    f,
+                    "indirect sample clamp must be a positive number but was {}",
+                    max
+                )
+            }
+            SettingsError::InvalidExposure(exposure) => {
+                write!(
+                    f,
+                    "exposure must be a non-negative number but was {}",
+                    exposure
+                )
+            }
+            SettingsError::InvalidWhiteBalance(gains) => {
+                write!(
+                    f,
+                    "white balance gains must be non-negative numbers but were {:?}",
+                    gains
+                )
+            }
+            SettingsError::InvalidShadowRayEpsilon(epsilon) => {
+                write!(
+                    f,
+                    "shadow ray epsilon must be a positive number but was {}",
+                    epsilon
+                )
+            }
+        }
+    }
+}
+
+impl Error for SettingsError {}
+
+/// An exact image size in pixels.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct Resolution {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Resolution {
+    /// The ratio of the width to the height.
+    pub fn aspect_ratio(&self) -> f64 {
+        self.width as f64 / self.height as f64
+    }
+}
+
+/// Error returned when a Resolution can't be parsed from a string.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ParseResolutionError {
+    /// The string wasn't two dimensions separated by an x, holds the offending string
+    InvalidFormat(String),
+    /// One of the dimensions wasn't a whole number above zero, holds the offending dimension
+    InvalidDimension(String),
+}
+
+impl fmt::Display for ParseResolutionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseResolutionError::InvalidFormat(resolution) => {
+                write!(
+                    f,
+                    "expected a resolution like 800x600 but found '{}'",
+                    resolution
+                )
+            }
+            ParseResolutionError::InvalidDimension(dimension) => {
+                write!(
+                    f,
+                    "dimension '{}' is not a positive whole number",
+                    dimension
+                )
+            }
+        }
+    }
+}
+
+impl Error for ParseResolutionError {}
+
+// Allows parsing resolutions from strings such as "800x600", e.g. for command line arguments
+impl FromStr for Resolution {
+    type Err = ParseResolutionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let dimensions: Vec<&str> = s.split('x').map(|d| d.trim()).collect();
+
+        if dimensions.len() != 2 {
+            return Err(ParseResolutionError::InvalidFormat(s.to_string()));
+        }
+
+        let parse = |d: &str| match d.parse::<u32>() {
+            Ok(dimension) if dimension > 0 => Ok(dimension),
+            _ => Err(ParseResolutionError::InvalidDimension(d.to_string())),
+        };
+
+        Ok(Resolution {
+            width: parse(dimensions[0])?,
+            height: parse(dimensions[1])?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::ray::Ray;
+    use crate::sphere::Sphere;
+
+    #[test]
+    fn validate_default() {
+        let settings = RenderSettings::default();
+
+        assert_eq!(
+            settings.validate(),
+            Ok(()),
+            "RenderSettings::default().validate() ({:?}) should be Ok",
+            settings.validate()
+        );
+    }
+
+    #[test]
+    fn validate_zero_width() {
+        let settings = RenderSettings {
+            image_width: 0,
+            ..RenderSettings::default()
+        };
+
+        assert_eq!(
+            settings.validate(),
+            Err(SettingsError::ZeroWidth),
+            "validate() ({:?}) should reject a zero width",
+            settings.validate()
+        );
+    }
+
+    #[test]
+    fn validate_zero_height() {
+        let settings = RenderSettings {
+            image_height_override: Some(0),
+            ..RenderSettings::default()
+        };
+
+        assert_eq!(
+            settings.validate(),
+            Err(SettingsError::ZeroHeight),
+            "validate() ({:?}) should reject a zero height override",
+            settings.validate()
+        );
+    }
+
+    #[test]
+    fn validate_invalid_aspect_ratio() {
+        for aspect_ratio in [0.0, -1.5, f64::NAN].iter() {
+            let settings = RenderSettings {
+                aspect_ratio: *aspect_ratio,
+                ..RenderSettings::default()
+            };
+
+            // NaN never equals itself, so match on the variant rather than comparing the error
+            assert!(
+                matches!(
+                    settings.validate(),
+                    Err(SettingsError::InvalidAspectRatio(_))
+                ),
+                "validate() ({:?}) should reject an aspect ratio of {}",
+                settings.validate(),
+                aspect_ratio
+            );
+        }
+    }
+
+    #[test]
+    fn validate_zero_samples() {
+        let settings = RenderSettings {
+            samples_per_pixel: 0,
+            ..RenderSettings::default()
+        };
+
+        assert_eq!(
+            settings.validate(),
+            Err(SettingsError::ZeroSamples),
+            "validate() ({:?}) should reject zero samples",
+            settings.validate()
+        );
+    }
+
+    #[test]
+    fn validate_invalid_clamp_indirect() {
+        for max in [0.0, -1.0, f64::INFINITY, f64::NAN].iter() {
+            let settings = RenderSettings {
+                clamp_indirect: Some(*max),
+                ..RenderSettings::default()
+            };
+
+            assert!(
+                matches!(
+                    settings.validate(),
+                    Err(SettingsError::InvalidClampIndirect(_))
+                ),
+                "validate() ({:?}) should reject an indirect clamp of {}",
+                settings.validate(),
+                max
+            );
+        }
+    }
+
+    #[test]
+    fn validate_invalid_exposure() {
+        for exposure in [-1.0, f64::NAN, f64::INFINITY].iter() {
+            let settings = RenderSettings {
+                exposure: *exposure,
+                ..RenderSettings::default()
+            };
+
+            assert!(
+                matches!(settings.validate(), Err(SettingsError::InvalidExposure(_))),
+                "validate() ({:?}) should reject an exposure of {}",
+                settings.validate(),
+                exposure
+            );
+        }
+    }
+
+    #[test]
+    fn validate_invalid_white_balance() {
+        for white_balance in [Vec3::new(1, -1, 1), Vec3::new(1, 1, f64::INFINITY)].iter() {
+            let settings = RenderSettings {
+                white_balance: *white_balance,
+                ..RenderSettings::default()
+            };
+
+            assert_eq!(
+                settings.validate(),
+                Err(SettingsError::InvalidWhiteBalance(*white_balance)),
+                "validate() ({:?}) should reject a white balance of {:?}",
+                settings.validate(),
+                white_balance
+            );
+        }
+    }
+
+    #[test]
+    fn validate_invalid_shadow_ray_epsilon() {
+        for epsilon in [0.0, -0.001, f64::NAN].iter() {
+            let settings = RenderSettings {
+                shadow_ray_epsilon: *epsilon,
+                ..RenderSettings::default()
+            };
+
+            assert!(
+                matches!(
+                    settings.validate(),
+                    Err(SettingsError::InvalidShadowRayEpsilon(_))
+                ),
+                "validate() ({:?}) should reject a shadow ray epsilon of {}",
+                settings.validate(),
+                epsilon
+            );
+        }
+    }
+
+    #[test]
+    fn shadow_ray_epsilon() {
+        // A ray leaving the surface of a tiny sphere back through it, which only reaches the
+        // far side of the sphere if the epsilon is smaller than the sphere
+        let sphere = Sphere::new(Vec3::new(0, 0, 0), 0.01);
+        let ray = Ray::new(Vec3::new(0, 0, 0.01), Vec3::new(0, 0, -1));
+        let small = RenderSettings::default();
+        let large = RenderSettings {
+            shadow_ray_epsilon: 1.0,
+            ..RenderSettings::default()
+        };
+
+        assert!(
+            sphere
+                .hit_both(&ray, small.shadow_ray_epsilon, f64::INFINITY)
+                .is_some(),
+            "A shadow ray epsilon of {} should still hit the far side of the sphere",
+            small.shadow_ray_epsilon
+        );
+        assert_eq!(
+            sphere.hit_both(&ray, large.shadow_ray_epsilon, f64::INFINITY),
+            None,
+            "A shadow ray epsilon of {} should skip past the whole sphere",
+            large.shadow_ray_epsilon
+        );
+    }
+
+    #[test]
+    fn image_height() {
+        let settings = RenderSettings {
+            image_width: 400,
+            aspect_ratio: 16.0 / 9.0,
+            ..RenderSettings::default()
+        };
+
+        assert_eq!(
+            settings.image_height(),
+            225,
+            "image_height() ({}) should match expected ({})",
+            settings.image_height(),
+            225
+        );
+    }
+
+    #[test]
+    fn image_height_at_least_one() {
+        let settings = RenderSettings {
+            image_width: 1,
+            aspect_ratio: 16.0 / 9.0,
+            ..RenderSettings::default()
+        };
+
+        assert_eq!(
+            settings.image_height(),
+            1,
+            "image_height() ({}) should never be less than 1",
+            settings.image_height()
+        );
+    }
+
+    #[test]
+    fn image_height_extreme_aspect_ratio() {
+        let settings = RenderSettings {
+            image_width: 400,
+            aspect_ratio: 1000.0,
+            ..RenderSettings::default()
+        };
+
+        assert_eq!(
+            settings.image_height(),
+            1,
+            "image_height() ({}) should never be less than 1",
+            settings.image_height()
+        );
+    }
+
+    #[test]
+    fn color_grade_neutral() {
+        let settings = RenderSettings::default();
+        let color = Vec3::new(0.25, 0.5, 2);
+
+        assert_eq!(
+            settings.color_grade(color),
+            color,
+            "Default color_grade ({:?}) should leave the color unchanged ({:?})",
+            settings.color_grade(color),
+            color
+        );
+    }
+
+    #[test]
+    fn color_grade_exposure() {
+        let settings = RenderSettings {
+            exposure: 2.0,
+            ..RenderSettings::default()
+        };
+        let expected = Vec3::new(0.36, 0.36, 0.36);
+
+        assert_eq!(
+            settings.color_grade(Vec3::new(0.18, 0.18, 0.18)),
+            expected,
+            "color_grade ({:?}) should double the mid gray ({:?})",
+            settings.color_grade(Vec3::new(0.18, 0.18, 0.18)),
+            expected
+        );
+    }
+
+    #[test]
+    fn color_grade_white_balance() {
+        let settings = RenderSettings {
+            white_balance: Vec3::new(1, 1, 2),
+            ..RenderSettings::default()
+        };
+        let expected = Vec3::new(0.5, 0.5, 1);
+
+        assert_eq!(
+            settings.color_grade(Vec3::new(0.5, 0.5, 0.5)),
+            expected,
+            "color_grade ({:?}) should only boost the blue channel ({:?})",
+            settings.color_grade(Vec3::new(0.5, 0.5, 0.5)),
+            expected
+        );
+    }
+
+    #[test]
+    fn clamp_sample_disabled() {
+        let settings = RenderSettings::default();
+        let sample = Vec3::new(1000, 1000, 1000);
+
+        assert_eq!(
+            settings.clamp_sample(sample),
+            sample,
+            "Default clamp_sample ({:?}) should leave the sample unchanged ({:?})",
+            settings.clamp_sample(sample),
+            sample
+        );
+    }
+
+    #[test]
+    fn clamp_sample() {
+        let settings = RenderSettings {
+            clamp_indirect: Some(10.0),
+            ..RenderSettings::default()
+        };
+        let bright = Vec3::new(1000, 1000, 1000);
+        let dim = Vec3::new(2, 2, 2);
+        let expected = Vec3::new(10, 10, 10);
+
+        assert_eq!(
+            settings.clamp_sample(bright),
+            expected,
+            "clamp_sample ({:?}) should clamp to the configured max ({:?})",
+            settings.clamp_sample(bright),
+            expected
+        );
+        assert_eq!(
+            settings.clamp_sample(dim),
+            dim,
+            "clamp_sample ({:?}) should leave the sample unchanged ({:?})",
+            settings.clamp_sample(dim),
+            dim
+        );
+    }
+
+    #[test]
+    fn pixel_uv_corners() {
+        let settings = RenderSettings {
+            image_width: 400,
+            aspect_ratio: 16.0 / 9.0,
+            ..RenderSettings::default()
+        };
+
+        assert_eq!(
+            settings.pixel_uv(0, 0),
+            (0.0, 0.0),
+            "pixel_uv(0, 0) ({:?}) should be the bottom left corner",
+            settings.pixel_uv(0, 0)
+        );
+        assert_eq!(
+            settings.pixel_uv(399, 224),
+            (1.0, 1.0),
+            "pixel_uv(399, 224) ({:?}) should be the top right corner",
+            settings.pixel_uv(399, 224)
+        );
+    }
+
+    #[test]
+    fn pixel_uv_single_column() {
+        let settings = RenderSettings {
+            image_width: 1,
+            aspect_ratio: 16.0 / 9.0,
+            ..RenderSettings::default()
+        };
+
+        assert_eq!(
+            settings.pixel_uv(0, 0),
+            (0.0, 0.0),
+            "pixel_uv(0, 0) ({:?}) should be finite for a single column image",
+            settings.pixel_uv(0, 0)
+        );
+    }
+
+    #[test]
+    fn pixel_uv_single_row() {
+        let settings = RenderSettings {
+            image_width: 400,
+            aspect_ratio: 1000.0,
+            ..RenderSettings::default()
+        };
+
+        assert_eq!(
+            settings.pixel_uv(399, 0),
+            (1.0, 0.0),
+            "pixel_uv(399, 0) ({:?}) should be finite for a single row image",
+            settings.pixel_uv(399, 0)
+        );
+    }
+
+    #[test]
+    fn parse_resolution() {
+        let expected = Resolution {
+            width: 800,
+            height: 600,
+        };
+
+        assert_eq!(
+            "800x600".parse::<Resolution>(),
+            Ok(expected),
+            "\"800x600\" ({:?}) should match expected ({:?})",
+            "800x600".parse::<Resolution>(),
+            expected
+        );
+    }
+
+    #[test]
+    fn parse_resolution_invalid() {
+        let expected = ParseResolutionError::InvalidFormat("800".to_string());
+
+        assert_eq!(
+            "800".parse::<Resolution>(),
+            Err(expected.clone()),
+            "\"800\" ({:?}) should be rejected with ({:?})",
+            "800".parse::<Resolution>(),
+            expected
+        );
+
+        let expected = ParseResolutionError::InvalidDimension("0".to_string());
+
+        assert_eq!(
+            "800x0".parse::<Resolution>(),
+            Err(expected.clone()),
+            "\"800x0\" ({:?}) should be rejected with ({:?})",
+            "800x0".parse::<Resolution>(),
+            expected
+        );
+    }
+
+    #[test]
+    fn set_resolution() {
+        let mut settings = RenderSettings::default();
+        settings.set_resolution("800x600".parse().unwrap());
+
+        assert_eq!(
+            (settings.image_width, settings.image_height()),
+            (800, 600),
+            "Image size ({:?}) should match the resolution exactly",
+            (settings.image_width, settings.image_height())
+        );
+        assert_eq!(
+            settings.aspect_ratio,
+            4.0 / 3.0,
+            "aspect_ratio ({}) should match expected ({})",
+            settings.aspect_ratio,
+            4.0 / 3.0
+        );
+    }
+}
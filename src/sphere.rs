@@ -0,0 +1,154 @@
+use crate::ray::Ray;
+use crate::vec3::Vec3;
+
+/// A sphere described by its center and radius.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct Sphere {
+    pub center: Vec3,
+    pub radius: f64,
+}
+
+impl Sphere {
+    pub fn new(center: Vec3, radius: f64) -> Sphere {
+        Sphere { center, radius }
+    }
+
+    /// Returns where the ray enters and leaves the sphere, limited to the range t_min..t_max.
+    ///
+    /// The near and far roots are clamped into the range, so a ray starting inside the
+    /// sphere enters it at t_min. Returns None if the ray misses the sphere entirely, or
+    /// the part of the ray inside the sphere lies completely outside of the range.
+    ///
+    /// * `ray` - The ray to check against the sphere
+    /// * `t_min` - The closest distance along the ray to accept
+    /// * `t_max` - The furthest distance along the ray to accept
+    pub fn hit_both(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<(f64, f64)> {
+        // Any point P on the sphere satisfies (P-C)*(P-C) = R*R, where C is the center of
+        // the sphere. The ray itself can be defined as P(t) = A + tb where A is the origin
+        // and b is the direction, so substituting it in and expanding gives a quadratic
+        // in t whose roots are the distances where the ray crosses the surface.
+        let oc = ray.origin - self.center;
+        let a = ray.direction.length_squared();
+        let b = 2.0 * oc.dot(ray.direction);
+        let c = oc.length_squared() - self.radius * self.radius;
+        let discriminant = b * b - 4.0 * a * c;
+
+        if discriminant < 0.0 {
+            return None;
+        }
+
+        let root = discriminant.sqrt();
+        let near = (-b - root) / (2.0 * a);
+        let far = (-b + root) / (2.0 * a);
+
+        if near > t_max || far < t_min {
+            return None;
+        }
+
+        Some((near.max(t_min), far.min(t_max)))
+    }
+
+    /// The unit length normal of the sphere at a point on its surface.
+    ///
+    /// Dividing by the radius instead of normalizing means a sphere with a negative radius
+    /// has normals pointing inwards. The surface is the same either way, since hit_both only
+    /// uses the radius squared, which is how a hollow glass bubble can be made by putting a
+    /// negative radius sphere inside a normal one.
+    ///
+    /// * `p` - The point on the surface of the sphere
+    pub fn outward_normal(&self, p: Vec3) -> Vec3 {
+        (p - self.center) / self.radius
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn hit_both_through_center() {
+        let sphere = Sphere::new(Vec3::new(0, 0, -5), 1.0);
+        let ray = Ray::new(Vec3::new(0, 0, 0), Vec3::new(0, 0, -1));
+        let expected = Some((4.0, 6.0));
+
+        assert_eq!(
+            sphere.hit_both(&ray, 0.0, f64::INFINITY),
+            expected,
+            "Sphere.hit_both ({:?}) should be symmetric about the center distance ({:?})",
+            sphere.hit_both(&ray, 0.0, f64::INFINITY),
+            expected
+        );
+    }
+
+    #[test]
+    fn hit_both_miss() {
+        let sphere = Sphere::new(Vec3::new(0, 0, -5), 1.0);
+        let ray = Ray::new(Vec3::new(0, 0, 0), Vec3::new(0, 1, 0));
+
+        assert_eq!(
+            sphere.hit_both(&ray, 0.0, f64::INFINITY),
+            None,
+            "Sphere.hit_both ({:?}) should be None for a ray that misses",
+            sphere.hit_both(&ray, 0.0, f64::INFINITY)
+        );
+    }
+
+    #[test]
+    fn hit_both_from_inside() {
+        let sphere = Sphere::new(Vec3::new(0, 0, 0), 1.0);
+        let ray = Ray::new(Vec3::new(0, 0, 0), Vec3::new(0, 0, -1));
+        let expected = Some((0.001, 1.0));
+
+        assert_eq!(
+            sphere.hit_both(&ray, 0.001, f64::INFINITY),
+            expected,
+            "Sphere.hit_both ({:?}) should clamp the near root to t_min ({:?})",
+            sphere.hit_both(&ray, 0.001, f64::INFINITY),
+            expected
+        );
+    }
+
+    #[test]
+    fn outward_normal() {
+        let sphere = Sphere::new(Vec3::new(0, 0, 0), 0.5);
+        let expected = Vec3::new(1, 0, 0);
+
+        assert_eq!(
+            sphere.outward_normal(Vec3::new(0.5, 0, 0)),
+            expected,
+            "Sphere.outward_normal ({:?}) should point away from the center ({:?})",
+            sphere.outward_normal(Vec3::new(0.5, 0, 0)),
+            expected
+        );
+    }
+
+    #[test]
+    fn outward_normal_negative_radius() {
+        // A hollow bubble, made of a thin shell with a negative radius sphere inside it
+        let outer = Sphere::new(Vec3::new(0, 0, 0), 0.5);
+        let inner = Sphere::new(Vec3::new(0, 0, 0), -0.4);
+        let ray = Ray::new(Vec3::new(-1, 0, 0), Vec3::new(1, 0, 0));
+
+        let (outer_t, _) = outer.hit_both(&ray, 0.0, f64::INFINITY).unwrap();
+        let (inner_t, _) = inner.hit_both(&ray, 0.0, f64::INFINITY).unwrap();
+        let outer_normal = outer.outward_normal(ray.at(outer_t));
+        let inner_normal = inner.outward_normal(ray.at(inner_t));
+
+        assert!(
+            outer_normal.dot(ray.at(outer_t) - outer.center) > 0.0,
+            "The outer sphere's normal ({:?}) should face away from the center",
+            outer_normal
+        );
+        assert!(
+            inner_normal.dot(ray.at(inner_t) - inner.center) < 0.0,
+            "The inner sphere's normal ({:?}) should face towards the center",
+            inner_normal
+        );
+        assert!(
+            (inner_normal.length() - 1.0).abs() < 1e-10,
+            "The inner sphere's normal ({:?}) should be unit length",
+            inner_normal
+        );
+    }
+}
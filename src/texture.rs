@@ -0,0 +1,99 @@
+/// How texture coordinates outside of 0-1 are brought back into range.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum WrapMode {
+    /// Tiles the texture, so 1.25 is the same as 0.25
+    Repeat,
+    /// Stretches the edge of the texture, so anything above 1 is 1 and anything below 0 is 0
+    Clamp,
+    /// Tiles the texture but flips every other tile, so 1.25 is the same as 0.75
+    Mirror,
+}
+
+impl WrapMode {
+    /// Maps a texture coordinate into the range 0-1.
+    ///
+    /// * `t` - The u or v texture coordinate to wrap
+    pub fn apply(&self, t: f64) -> f64 {
+        match self {
+            WrapMode::Repeat => t - t.floor(),
+            WrapMode::Clamp => t.clamp(0.0, 1.0),
+            WrapMode::Mirror => {
+                // Mirroring repeats every two tiles, with the second tile running backwards
+                let t = t.rem_euclid(2.0);
+                if t > 1.0 {
+                    2.0 - t
+                } else {
+                    t
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn wrap_repeat() {
+        assert_eq!(
+            WrapMode::Repeat.apply(1.5),
+            0.5,
+            "WrapMode::Repeat.apply(1.5) ({}) should match expected ({})",
+            WrapMode::Repeat.apply(1.5),
+            0.5
+        );
+        assert_eq!(
+            WrapMode::Repeat.apply(-0.25),
+            0.75,
+            "WrapMode::Repeat.apply(-0.25) ({}) should match expected ({})",
+            WrapMode::Repeat.apply(-0.25),
+            0.75
+        );
+    }
+
+    #[test]
+    fn wrap_clamp() {
+        assert_eq!(
+            WrapMode::Clamp.apply(1.5),
+            1.0,
+            "WrapMode::Clamp.apply(1.5) ({}) should match expected ({})",
+            WrapMode::Clamp.apply(1.5),
+            1.0
+        );
+        assert_eq!(
+            WrapMode::Clamp.apply(-0.25),
+            0.0,
+            "WrapMode::Clamp.apply(-0.25) ({}) should match expected ({})",
+            WrapMode::Clamp.apply(-0.25),
+            0.0
+        );
+    }
+
+    #[test]
+    fn wrap_mirror() {
+        // 1.25 is a quarter of the way back from the far edge, so it lands on 0.75
+        assert_eq!(
+            WrapMode::Mirror.apply(1.25),
+            0.75,
+            "WrapMode::Mirror.apply(1.25) ({}) should match expected ({})",
+            WrapMode::Mirror.apply(1.25),
+            0.75
+        );
+        assert_eq!(
+            WrapMode::Mirror.apply(1.5),
+            0.5,
+            "WrapMode::Mirror.apply(1.5) ({}) should match expected ({})",
+            WrapMode::Mirror.apply(1.5),
+            0.5
+        );
+        assert_eq!(
+            WrapMode::Mirror.apply(-0.25),
+            0.25,
+            "WrapMode::Mirror.apply(-0.25) ({}) should match expected ({})",
+            WrapMode::Mirror.apply(-0.25),
+            0.25
+        );
+    }
+}
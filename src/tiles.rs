@@ -0,0 +1,170 @@
+/// The order the tiles of an image are rendered in.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum TileOrder {
+    /// Row by row, left to right
+    Scanline,
+    /// Along a Hilbert curve, which keeps consecutive tiles next to each other
+    Hilbert,
+    /// Spiralling outwards from the center of the image
+    Spiral,
+}
+
+/// Lists every tile of an image exactly once, in the given order.
+///
+/// Tiles are given as (column, row) pairs.
+///
+/// * `order` - The order to visit the tiles in
+/// * `tiles_x` - The number of columns of tiles
+/// * `tiles_y` - The number of rows of tiles
+pub fn tile_order(order: TileOrder, tiles_x: u32, tiles_y: u32) -> Vec<(u32, u32)> {
+    match order {
+        TileOrder::Scanline => (0..tiles_y)
+            .flat_map(|y| (0..tiles_x).map(move |x| (x, y)))
+            .collect(),
+        TileOrder::Hilbert => hilbert_order(tiles_x, tiles_y),
+        TileOrder::Spiral => spiral_order(tiles_x, tiles_y),
+    }
+}
+
+// The Hilbert curve only fills power of two squares, so walk the smallest one covering the
+// image and skip any tiles that fall outside of it
+fn hilbert_order(tiles_x: u32, tiles_y: u32) -> Vec<(u32, u32)> {
+    let n = tiles_x.max(tiles_y).next_power_of_two() as u64;
+
+    (0..n * n)
+        .map(|d| hilbert_point(n, d))
+        .filter(|&(x, y)| x < tiles_x && y < tiles_y)
+        .collect()
+}
+
+// Converts a distance along the Hilbert curve filling an n by n square into a point
+fn hilbert_point(n: u64, d: u64) -> (u32, u32) {
+    let (mut x, mut y) = (0, 0);
+    let mut t = d;
+    let mut s = 1;
+
+    while s < n {
+        let rx = 1 & (t / 2);
+        let ry = 1 & (t ^ rx);
+
+        // Each quadrant is a rotated or flipped copy of the whole curve
+        if ry == 0 {
+            if rx == 1 {
+                x = s - 1 - x;
+                y = s - 1 - y;
+            }
+            std::mem::swap(&mut x, &mut y);
+        }
+
+        x += s * rx;
+        y += s * ry;
+        t /= 4;
+        s *= 2;
+    }
+
+    (x as u32, y as u32)
+}
+
+// Walks a square spiral out from the center tile (right, down, left, up with the side
+// growing every other turn) until every tile inside the image has been visited
+fn spiral_order(tiles_x: u32, tiles_y: u32) -> Vec<(u32, u32)> {
+    let total = (tiles_x * tiles_y) as usize;
+    let mut tiles = Vec::with_capacity(total);
+
+    let (mut x, mut y) = ((tiles_x as i64 - 1) / 2, (tiles_y as i64 - 1) / 2);
+    let directions = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+    let mut side = 1;
+    let mut turn = 0;
+
+    while tiles.len() < total {
+        let (dx, dy) = directions[turn % 4];
+
+        for _ in 0..side {
+            if x >= 0 && y >= 0 && x < tiles_x as i64 && y < tiles_y as i64 {
+                tiles.push((x as u32, y as u32));
+            }
+            x += dx;
+            y += dy;
+        }
+
+        turn += 1;
+        if turn % 2 == 0 {
+            side += 1;
+        }
+    }
+
+    tiles
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn every_tile_once() {
+        for order in [TileOrder::Scanline, TileOrder::Hilbert, TileOrder::Spiral].iter() {
+            for &(tiles_x, tiles_y) in [(1, 1), (4, 4), (5, 3), (2, 7), (0, 3)].iter() {
+                let tiles = tile_order(*order, tiles_x, tiles_y);
+                let unique: HashSet<(u32, u32)> = tiles.iter().cloned().collect();
+
+                assert_eq!(
+                    tiles.len(),
+                    (tiles_x * tiles_y) as usize,
+                    "{:?} order for {}x{} tiles ({:?}) should visit every tile",
+                    order,
+                    tiles_x,
+                    tiles_y,
+                    tiles
+                );
+                assert_eq!(
+                    unique.len(),
+                    tiles.len(),
+                    "{:?} order for {}x{} tiles ({:?}) should visit each tile only once",
+                    order,
+                    tiles_x,
+                    tiles_y,
+                    tiles
+                );
+                assert!(
+                    tiles.iter().all(|&(x, y)| x < tiles_x && y < tiles_y),
+                    "{:?} order for {}x{} tiles ({:?}) should stay inside the image",
+                    order,
+                    tiles_x,
+                    tiles_y,
+                    tiles
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn hilbert_neighbours() {
+        let tiles = tile_order(TileOrder::Hilbert, 8, 8);
+
+        for pair in tiles.windows(2) {
+            let distance = (pair[0].0 as i64 - pair[1].0 as i64).abs()
+                + (pair[0].1 as i64 - pair[1].1 as i64).abs();
+
+            assert_eq!(
+                distance, 1,
+                "Consecutive Hilbert tiles ({:?}) should be next to each other",
+                pair
+            );
+        }
+    }
+
+    #[test]
+    fn spiral_starts_at_center() {
+        let tiles = tile_order(TileOrder::Spiral, 5, 3);
+
+        assert_eq!(
+            tiles[0],
+            (2, 1),
+            "The first spiral tile ({:?}) should be the center tile ({:?})",
+            tiles[0],
+            (2, 1)
+        );
+    }
+}
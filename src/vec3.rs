@@ -1,10 +1,17 @@
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::iter::Sum;
 use std::ops::{Add, Div, Index, Mul, Sub};
+use std::str::FromStr;
 
 // The derive means we don't need to manually implement it.
 // Copy means this thing is essentially treated as a value type, and a copy of all fields
 // is made on move (the original is not moved).
 // Clone does a similar thing but is more explicit (.clone instead of doing it automatically).
-#[derive(Debug, PartialEq, Copy, Clone)]
+// Default gives a vector of all zeros, the same as Vec3::ZERO.
+#[derive(Debug, PartialEq, Copy, Clone, Default)]
 pub struct Vec3 {
     pub x: f64,
     pub y: f64,
@@ -12,6 +19,41 @@ pub struct Vec3 {
 }
 
 impl Vec3 {
+    /// A vector with all components set to 0.
+    pub const ZERO: Vec3 = Vec3 {
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+    };
+
+    /// A vector with all components set to 1.
+    pub const ONE: Vec3 = Vec3 {
+        x: 1.0,
+        y: 1.0,
+        z: 1.0,
+    };
+
+    /// A unit vector along the x axis.
+    pub const UNIT_X: Vec3 = Vec3 {
+        x: 1.0,
+        y: 0.0,
+        z: 0.0,
+    };
+
+    /// A unit vector along the y axis.
+    pub const UNIT_Y: Vec3 = Vec3 {
+        x: 0.0,
+        y: 1.0,
+        z: 0.0,
+    };
+
+    /// A unit vector along the z axis.
+    pub const UNIT_Z: Vec3 = Vec3 {
+        x: 0.0,
+        y: 0.0,
+        z: 1.0,
+    };
+
     /// Creates a new Vec3.
     ///
     /// Given a type which can be converted into an f64, creates a Vec3.
@@ -66,7 +108,199 @@ impl Vec3 {
 
     pub fn normalized(&self) -> Vec3 {
         let length = self.length();
-        self.div(length)
+        *self / length
+    }
+
+    /// Normalized copy of the vector, or None if the vector has zero length.
+    ///
+    /// Normalizing a zero length vector would divide by zero and produce NaNs.
+    pub fn try_normalized(&self) -> Option<Vec3> {
+        if self.length_squared() == 0.0 {
+            None
+        } else {
+            Some(self.normalized())
+        }
+    }
+
+    /// Normalizes the vector in place, returning its length before normalizing.
+    ///
+    /// A zero length vector is left unchanged, as with try_normalized.
+    pub fn normalize(&mut self) -> f64 {
+        let length = self.length();
+
+        if let Some(normalized) = self.try_normalized() {
+            *self = normalized;
+        }
+
+        length
+    }
+
+    /// Square root of each component of the vector.
+    pub fn sqrt(&self) -> Vec3 {
+        Vec3 {
+            x: self.x.sqrt(),
+            y: self.y.sqrt(),
+            z: self.z.sqrt(),
+        }
+    }
+
+    /// Absolute value of each component of the vector.
+    pub fn abs(&self) -> Vec3 {
+        Vec3 {
+            x: self.x.abs(),
+            y: self.y.abs(),
+            z: self.z.abs(),
+        }
+    }
+
+    /// Raises each component of the vector to a power.
+    ///
+    /// * `n` - The power to raise each component to
+    pub fn powf(&self, n: f64) -> Vec3 {
+        Vec3 {
+            x: self.x.powf(n),
+            y: self.y.powf(n),
+            z: self.z.powf(n),
+        }
+    }
+
+    /// Converts a linear color into 8-bit RGB values.
+    ///
+    /// Averages the color over the number of samples taken, optionally gamma corrects it,
+    /// then clamps each component to 0-1 before scaling it to 0-255.
+    ///
+    /// * `samples` - The number of samples summed together to make this color
+    /// * `gamma` - Whether to gamma correct the color (using a gamma of 2)
+    pub fn to_rgb8(&self, samples: u32, gamma: bool) -> [u8; 3] {
+        let mut color = *self / samples.max(1);
+
+        if gamma {
+            color = color.sqrt();
+        }
+
+        // Convert the color from 0-1 to 0-255
+        [
+            (color.x.clamp(0.0, 1.0) * 255.99) as u8,
+            (color.y.clamp(0.0, 1.0) * 255.99) as u8,
+            (color.z.clamp(0.0, 1.0) * 255.99) as u8,
+        ]
+    }
+
+    /// Converts 8-bit RGB values into a color with components between 0 and 1.
+    ///
+    /// * `rgb` - The red, green and blue values
+    pub fn from_rgb8(rgb: [u8; 3]) -> Vec3 {
+        Vec3::new(rgb[0], rgb[1], rgb[2]) / 255
+    }
+
+    /// Projection of this vector onto another vector.
+    ///
+    /// Returns the part of this vector which points in the same direction as the other vector.
+    /// A zero length vector has no direction, so projecting onto it returns the zero vector
+    /// rather than dividing by zero.
+    ///
+    /// * `onto` - The vector to project onto
+    pub fn project_onto(&self, onto: Vec3) -> Vec3 {
+        let length_squared = onto.length_squared();
+
+        if length_squared == 0.0 {
+            return Vec3::ZERO;
+        }
+
+        onto * (self.dot(onto) / length_squared)
+    }
+
+    /// Rejection of this vector from another vector.
+    ///
+    /// Returns the part of this vector which is perpendicular to the other vector, so the
+    /// projection and rejection always sum back to the original vector.
+    ///
+    /// * `from` - The vector to reject from
+    pub fn reject_from(&self, from: Vec3) -> Vec3 {
+        *self - self.project_onto(from)
+    }
+
+    /// Angle between this and another vector in radians.
+    ///
+    /// The angle to or from a zero length vector isn't defined, so it returns NaN.
+    ///
+    /// * `other` - The other vector to measure the angle to
+    pub fn angle_between(&self, other: Vec3) -> f64 {
+        let cos_theta = self.dot(other) / (self.length() * other.length());
+
+        // Floating point error can push the cosine slightly outside of -1 to 1
+        // for (anti)parallel vectors, which would make acos return NaN
+        cos_theta.clamp(-1.0, 1.0).acos()
+    }
+
+    /// The largest component of the vector.
+    pub fn max_component(&self) -> f64 {
+        self.x.max(self.y).max(self.z)
+    }
+
+    /// The smallest component of the vector.
+    pub fn min_component(&self) -> f64 {
+        self.x.min(self.y).min(self.z)
+    }
+
+    /// Index of the largest component of the vector (0 for x, 1 for y and 2 for z).
+    ///
+    /// When several components are equally large the first of them is returned.
+    pub fn max_component_index(&self) -> usize {
+        if self.x >= self.y && self.x >= self.z {
+            0
+        } else if self.y >= self.z {
+            1
+        } else {
+            2
+        }
+    }
+
+    /// Luminance of the vector when treated as a linear RGB color.
+    ///
+    /// Uses the Rec. 709 weights, which reflect how much brighter green looks to the eye
+    /// than red, and red than blue.
+    pub fn luminance(&self) -> f64 {
+        0.2126 * self.x + 0.7152 * self.y + 0.0722 * self.z
+    }
+
+    /// Applies a function to each component of the vector.
+    ///
+    /// * `f` - The function to apply to each component
+    pub fn map<F>(&self, f: F) -> Vec3
+    where
+        F: Fn(f64) -> f64,
+    {
+        Vec3 {
+            x: f(self.x),
+            y: f(self.y),
+            z: f(self.z),
+        }
+    }
+
+    /// Reflection of this vector about a surface normal.
+    ///
+    /// Keeps the part of the vector along the surface and flips the part along the normal,
+    /// like a ray bouncing off a mirror.
+    ///
+    /// * `n` - The unit length normal of the surface being reflected off
+    pub fn reflect(&self, n: Vec3) -> Vec3 {
+        *self - 2.0 * self.dot(n) * n
+    }
+
+    /// Refraction of this vector as it passes through a surface, using Snell's law.
+    ///
+    /// The vector and normal need to be unit length and point against each other, i.e.
+    /// the normal faces the side the vector is coming from. Total internal reflection
+    /// isn't handled here, so check for it before refracting.
+    ///
+    /// * `n` - The unit length normal of the surface being passed through
+    /// * `etai_over_etat` - The refractive index the ray leaves divided by the one it enters
+    pub fn refract(&self, n: Vec3, etai_over_etat: f64) -> Vec3 {
+        let cos_theta = (-self.dot(n)).min(1.0);
+        let r_out_perp = etai_over_etat * (*self + cos_theta * n);
+        let r_out_parallel = -(1.0 - r_out_perp.length_squared()).abs().sqrt() * n;
+        r_out_perp + r_out_parallel
     }
 }
 
@@ -84,6 +318,129 @@ impl Index<usize> for Vec3 {
     }
 }
 
+/// A Vec3 which can be used as a key in a HashMap, e.g. to deduplicate mesh vertices.
+///
+/// f64 isn't Hash or Eq, so instead two vectors are equal when their components have
+/// exactly the same bits. Negative zero is treated as positive zero so the two are equal.
+#[derive(Debug, Copy, Clone)]
+pub struct OrderedVec3(pub Vec3);
+
+impl OrderedVec3 {
+    /// The bits of each component, with -0.0 mapped onto 0.0.
+    fn key(&self) -> [u64; 3] {
+        // Adding 0.0 turns -0.0 into 0.0 while leaving every other value unchanged
+        let bits = |c: f64| (c + 0.0).to_bits();
+        [bits(self.0.x), bits(self.0.y), bits(self.0.z)]
+    }
+}
+
+impl From<Vec3> for OrderedVec3 {
+    fn from(v: Vec3) -> Self {
+        OrderedVec3(v)
+    }
+}
+
+impl PartialEq for OrderedVec3 {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for OrderedVec3 {}
+
+impl Hash for OrderedVec3 {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+/// Error returned when a Vec3 can't be parsed from a string or converted from a slice.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ParseVec3Error {
+    /// There weren't exactly three components, holds how many were found
+    WrongComponentCount(usize),
+    /// One of the components wasn't a number, holds the offending component
+    InvalidComponent(String),
+}
+
+impl fmt::Display for ParseVec3Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseVec3Error::WrongComponentCount(count) => {
+                write!(f, "expected 3 components (e.g. 1,2,3) but found {}", count)
+            }
+            ParseVec3Error::InvalidComponent(component) => {
+                write!(f, "component '{}' is not a valid number", component)
+            }
+        }
+    }
+}
+
+impl Error for ParseVec3Error {}
+
+// Allows parsing vectors from strings such as "13,2,3", e.g. for command line arguments
+impl FromStr for Vec3 {
+    type Err = ParseVec3Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let components: Vec<&str> = s.split(',').map(|c| c.trim()).collect();
+
+        if components.len() != 3 {
+            return Err(ParseVec3Error::WrongComponentCount(components.len()));
+        }
+
+        let parse = |c: &str| {
+            c.parse::<f64>()
+                .map_err(|_| ParseVec3Error::InvalidComponent(c.to_string()))
+        };
+
+        Ok(Vec3::new(
+            parse(components[0])?,
+            parse(components[1])?,
+            parse(components[2])?,
+        ))
+    }
+}
+
+// Allows iterating over the components in x, y, z order, e.g. for c in my_vec
+impl IntoIterator for Vec3 {
+    type Item = f64;
+    type IntoIter = std::array::IntoIter<f64, 3>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIterator::into_iter([self.x, self.y, self.z])
+    }
+}
+
+// Allows iterating over the components of a borrowed vector, e.g. for c in &my_vec
+impl IntoIterator for &Vec3 {
+    type Item = f64;
+    type IntoIter = std::array::IntoIter<f64, 3>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        (*self).into_iter()
+    }
+}
+
+// Allows converting a color into padded RGBA floats (with an alpha of 1.0) for uploading to a GPU
+impl From<Vec3> for [f32; 4] {
+    fn from(color: Vec3) -> Self {
+        [color.x as f32, color.y as f32, color.z as f32, 1.0]
+    }
+}
+
+// Allows converting a slice of exactly three numbers into a vector, e.g. Vec3::try_from(&data[..3])
+impl TryFrom<&[f64]> for Vec3 {
+    type Error = ParseVec3Error;
+
+    fn try_from(components: &[f64]) -> Result<Self, Self::Error> {
+        match components {
+            [x, y, z] => Ok(Vec3::new(*x, *y, *z)),
+            _ => Err(ParseVec3Error::WrongComponentCount(components.len())),
+        }
+    }
+}
+
 // Allows doing things like my_vec + 2.0 to work, along with my_vec + 2i32
 impl<T> Add<T> for Vec3
 where
@@ -111,13 +468,11 @@ impl Add for Vec3 {
     }
 }
 
-// This allows bidirectional adds (e.g. 3.0 + my_vector), currently unsure of a better way to do
-// so, so only implemented for floats so far
-impl Add<Vec3> for f64 {
+// Allows references to vectors to be summed together, e.g. &my_vec + &my_other_vec
+impl Add<&Vec3> for &Vec3 {
     type Output = Vec3;
-
-    fn add(self, other: Vec3) -> Self::Output {
-        other + self
+    fn add(self, other: &Vec3) -> Vec3 {
+        *self + *other
     }
 }
 
@@ -148,13 +503,11 @@ impl Sub for Vec3 {
     }
 }
 
-// This allows bidirectional subs (e.g. 3.0 - my_vector), currently unsure of a better way to do
-// so, so only implemented for floats so far
-impl Sub<Vec3> for f64 {
+// Allows references to vectors to be subtracted from each other, e.g. &my_vec - &my_other_vec
+impl Sub<&Vec3> for &Vec3 {
     type Output = Vec3;
-
-    fn sub(self, other: Vec3) -> Self::Output {
-        other - self
+    fn sub(self, other: &Vec3) -> Vec3 {
+        *self - *other
     }
 }
 
@@ -185,16 +538,11 @@ impl Mul for Vec3 {
     }
 }
 
-// This allows bidirectional multiplys (e.g. 3.0 * my_vector), currently unsure of a better way to do
-// so, so only implemented for floats so far
-// Note: I didn't implement division (e.g. 3 / my_vector) despite an inverse via multiplication
-// being possible because I've never seen a number divided by a vector and am unsure if any
-// specific maths depends on that being weirdly
-impl Mul<Vec3> for f64 {
+// Allows references to vectors to be multiplied together, e.g. &my_vec * &my_other_vec
+impl Mul<&Vec3> for &Vec3 {
     type Output = Vec3;
-
-    fn mul(self, other: Vec3) -> Self::Output {
-        other * self
+    fn mul(self, other: &Vec3) -> Vec3 {
+        *self * *other
     }
 }
 
@@ -225,6 +573,68 @@ impl Div for Vec3 {
     }
 }
 
+// Allows references to vectors to be divided by each other, e.g. &my_vec / &my_other_vec
+impl Div<&Vec3> for &Vec3 {
+    type Output = Vec3;
+    fn div(self, other: &Vec3) -> Vec3 {
+        *self / *other
+    }
+}
+
+// This allows bidirectional operators (e.g. 3.0 * my_vector or 3 * my_vector). Rust won't allow a
+// generic impl<T> Mul<Vec3> for T since the left hand side would be a foreign type, so instead
+// this macro implements them for each of the common scalar types.
+// Note: I didn't implement division (e.g. 3 / my_vector) despite an inverse via multiplication
+// being possible because I've never seen a number divided by a vector and am unsure if any
+// specific maths depends on that being weirdly
+macro_rules! impl_scalar_lhs_ops {
+    ($($scalar:ty),*) => {
+        $(
+            impl Add<Vec3> for $scalar {
+                type Output = Vec3;
+
+                fn add(self, other: Vec3) -> Self::Output {
+                    other + self
+                }
+            }
+
+            // Unlike add and mul this isn't commutative, so each component is subtracted from
+            // the scalar (e.g. 3 - my_vector is (3 - x, 3 - y, 3 - z))
+            impl Sub<Vec3> for $scalar {
+                type Output = Vec3;
+
+                fn sub(self, other: Vec3) -> Self::Output {
+                    Vec3::new(self, self, self) - other
+                }
+            }
+
+            impl Mul<Vec3> for $scalar {
+                type Output = Vec3;
+
+                fn mul(self, other: Vec3) -> Self::Output {
+                    other * self
+                }
+            }
+        )*
+    };
+}
+
+impl_scalar_lhs_ops!(f64, f32, i32, u32, i16, u16, i8, u8);
+
+// Allows summing an iterator of vectors, e.g. samples.iter().sum::<Vec3>()
+impl Sum for Vec3 {
+    fn sum<I: Iterator<Item = Vec3>>(iter: I) -> Vec3 {
+        iter.fold(Vec3::ZERO, |total, v| total + v)
+    }
+}
+
+// Allows summing an iterator of references to vectors without copying them first
+impl<'a> Sum<&'a Vec3> for Vec3 {
+    fn sum<I: Iterator<Item = &'a Vec3>>(iter: I) -> Vec3 {
+        iter.fold(Vec3::ZERO, |total, v| total + *v)
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -566,7 +976,7 @@ mod tests {
             z: 3.0,
         };
 
-        v[3];
+        let _ = v[3];
     }
 
     #[test]
@@ -582,4 +992,713 @@ mod tests {
             expected
         );
     }
+
+    #[test]
+    fn ref_vector_vector() {
+        let v = Vec3::new(3, 2, 15);
+        let v2 = Vec3::new(5, 2.0, 3);
+
+        // Mirrors generic code which only holds references into a collection
+        let (a, b) = (&v, &v2);
+
+        assert_eq!(
+            a + b,
+            v + v2,
+            "&V + &V2 ({:?}) should match V + V2 ({:?})",
+            a + b,
+            v + v2
+        );
+        assert_eq!(
+            a - b,
+            v - v2,
+            "&V - &V2 ({:?}) should match V - V2 ({:?})",
+            a - b,
+            v - v2
+        );
+        assert_eq!(
+            a * b,
+            v * v2,
+            "&V * &V2 ({:?}) should match V * V2 ({:?})",
+            a * b,
+            v * v2
+        );
+        assert_eq!(
+            a / b,
+            v / v2,
+            "&V / &V2 ({:?}) should match V / V2 ({:?})",
+            a / b,
+            v / v2
+        );
+    }
+
+    #[test]
+    fn parse_vector() {
+        let expected = Vec3::new(1, 2, 3.5);
+
+        assert_eq!(
+            "1.0,2,3.5".parse::<Vec3>(),
+            Ok(expected),
+            "\"1.0,2,3.5\" ({:?}) should parse to expected ({:?})",
+            "1.0,2,3.5".parse::<Vec3>(),
+            expected
+        );
+    }
+
+    #[test]
+    fn parse_vector_too_few() {
+        let expected = ParseVec3Error::WrongComponentCount(2);
+
+        assert_eq!(
+            "1,2".parse::<Vec3>(),
+            Err(expected.clone()),
+            "\"1,2\" ({:?}) should be rejected with ({:?})",
+            "1,2".parse::<Vec3>(),
+            expected
+        );
+    }
+
+    #[test]
+    fn parse_vector_non_numeric() {
+        let expected = ParseVec3Error::InvalidComponent("x".to_string());
+
+        assert_eq!(
+            "1,x,3".parse::<Vec3>(),
+            Err(expected.clone()),
+            "\"1,x,3\" ({:?}) should be rejected with ({:?})",
+            "1,x,3".parse::<Vec3>(),
+            expected
+        );
+    }
+
+    #[test]
+    fn scalar_vector() {
+        let v = Vec3::new(1, 2, 3);
+        let expected_add = Vec3::new(3, 4, 5);
+        let expected_sub = Vec3::new(1, 0, -1);
+        let expected_mul = Vec3::new(2, 4, 6);
+
+        assert_eq!(
+            2 + v,
+            expected_add,
+            "2 + V ({:?}) should match expected ({:?})",
+            2 + v,
+            expected_add
+        );
+        assert_eq!(
+            2.0 + v,
+            2 + v,
+            "2.0 + V ({:?}) should match 2 + V ({:?})",
+            2.0 + v,
+            2 + v
+        );
+        assert_eq!(
+            2 - v,
+            expected_sub,
+            "2 - V ({:?}) should match expected ({:?})",
+            2 - v,
+            expected_sub
+        );
+        assert_eq!(
+            2.0 - v,
+            2 - v,
+            "2.0 - V ({:?}) should match 2 - V ({:?})",
+            2.0 - v,
+            2 - v
+        );
+        assert_eq!(
+            2 * v,
+            expected_mul,
+            "2 * V ({:?}) should match expected ({:?})",
+            2 * v,
+            expected_mul
+        );
+        assert_eq!(
+            2.0 * v,
+            2 * v,
+            "2.0 * V ({:?}) should match 2 * V ({:?})",
+            2.0 * v,
+            2 * v
+        );
+        assert_eq!(
+            2 * v,
+            v * 2,
+            "2 * V ({:?}) should match V * 2 ({:?})",
+            2 * v,
+            v * 2
+        );
+    }
+
+    #[test]
+    fn sqrt_vector() {
+        let v = Vec3::new(4, 9, 16);
+        let expected = Vec3::new(2, 3, 4);
+
+        assert_eq!(
+            v.sqrt(),
+            expected,
+            "V.sqrt() ({:?}) should match expected ({:?})",
+            v.sqrt(),
+            expected
+        );
+    }
+
+    #[test]
+    fn abs_vector() {
+        let v = Vec3::new(-1, 2, -3);
+        let expected = Vec3::new(1, 2, 3);
+
+        assert_eq!(
+            v.abs(),
+            expected,
+            "V.abs() ({:?}) should match expected ({:?})",
+            v.abs(),
+            expected
+        );
+    }
+
+    #[test]
+    fn powf_vector() {
+        let v = Vec3::new(2, 3, 4);
+        let expected = Vec3::new(8, 27, 64);
+
+        assert_eq!(
+            v.powf(3.0),
+            expected,
+            "V.powf(3.0) ({:?}) should match expected ({:?})",
+            v.powf(3.0),
+            expected
+        );
+    }
+
+    #[test]
+    fn rgb8_round_trip() {
+        for rgb in [[0, 0, 0], [255, 255, 255], [12, 128, 201]].iter() {
+            let round_trip = Vec3::from_rgb8(*rgb).to_rgb8(1, false);
+
+            assert_eq!(
+                round_trip, *rgb,
+                "Round trip ({:?}) should match original ({:?})",
+                round_trip, rgb
+            );
+        }
+    }
+
+    #[test]
+    fn to_rgb8_clamps() {
+        let v = Vec3::new(-0.5, 0.5, 2);
+        let expected = [0, 127, 255];
+
+        assert_eq!(
+            v.to_rgb8(1, false),
+            expected,
+            "V.to_rgb8(1, false) ({:?}) should match expected ({:?})",
+            v.to_rgb8(1, false),
+            expected
+        );
+    }
+
+    #[test]
+    fn to_rgb8_samples_gamma() {
+        // Four samples summing to 1.0 average to 0.25, which gamma corrects to 0.5
+        let v = Vec3::new(1, 1, 1);
+        let expected = [127, 127, 127];
+
+        assert_eq!(
+            v.to_rgb8(4, true),
+            expected,
+            "V.to_rgb8(4, true) ({:?}) should match expected ({:?})",
+            v.to_rgb8(4, true),
+            expected
+        );
+    }
+
+    #[test]
+    fn constants() {
+        assert_eq!(
+            Vec3::ZERO,
+            Vec3::new(0, 0, 0),
+            "Vec3::ZERO ({:?}) should match (0, 0, 0)",
+            Vec3::ZERO
+        );
+        assert_eq!(
+            Vec3::ONE,
+            Vec3::new(1, 1, 1),
+            "Vec3::ONE ({:?}) should match (1, 1, 1)",
+            Vec3::ONE
+        );
+        assert_eq!(
+            Vec3::UNIT_X,
+            Vec3::new(1, 0, 0),
+            "Vec3::UNIT_X ({:?}) should match (1, 0, 0)",
+            Vec3::UNIT_X
+        );
+        assert_eq!(
+            Vec3::UNIT_Y,
+            Vec3::new(0, 1, 0),
+            "Vec3::UNIT_Y ({:?}) should match (0, 1, 0)",
+            Vec3::UNIT_Y
+        );
+        assert_eq!(
+            Vec3::UNIT_Z,
+            Vec3::new(0, 0, 1),
+            "Vec3::UNIT_Z ({:?}) should match (0, 0, 1)",
+            Vec3::UNIT_Z
+        );
+    }
+
+    #[test]
+    fn default_vector() {
+        assert_eq!(
+            Vec3::default(),
+            Vec3::ZERO,
+            "Vec3::default() ({:?}) should match Vec3::ZERO ({:?})",
+            Vec3::default(),
+            Vec3::ZERO
+        );
+    }
+
+    fn hash_of(v: OrderedVec3) -> u64 {
+        use std::collections::hash_map::DefaultHasher;
+
+        let mut hasher = DefaultHasher::new();
+        v.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn ordered_vector_hash() {
+        let v = OrderedVec3(Vec3::new(0.1, 2, 3));
+        let v2 = OrderedVec3(Vec3::new(0.1, 2, 3));
+        let v3 = OrderedVec3(Vec3::new(0.1, 2, 3.0000001));
+
+        assert_eq!(v, v2, "V ({:?}) should equal V2 ({:?})", v, v2);
+        assert_eq!(
+            hash_of(v),
+            hash_of(v2),
+            "Hash of V ({:?}) should match hash of V2 ({:?})",
+            v,
+            v2
+        );
+        assert_ne!(v, v3, "V ({:?}) should not equal V3 ({:?})", v, v3);
+    }
+
+    #[test]
+    fn ordered_vector_negative_zero() {
+        let v = OrderedVec3(Vec3::new(0.0, 1, 2));
+        let v2 = OrderedVec3(Vec3::new(-0.0, 1, 2));
+
+        assert_eq!(v, v2, "V ({:?}) should equal V2 ({:?})", v, v2);
+        assert_eq!(
+            hash_of(v),
+            hash_of(v2),
+            "Hash of V ({:?}) should match hash of V2 ({:?})",
+            v,
+            v2
+        );
+    }
+
+    #[test]
+    fn ordered_vector_deduplicate() {
+        use std::collections::HashSet;
+
+        let vertices = [
+            Vec3::new(0, 0, 0),
+            Vec3::new(1, 0, 0),
+            Vec3::new(0, 0, 0),
+            Vec3::new(-0.0, 0, 0),
+        ];
+        let unique: HashSet<OrderedVec3> = vertices.iter().map(|v| OrderedVec3::from(*v)).collect();
+
+        assert_eq!(
+            unique.len(),
+            2,
+            "Unique vertices ({:?}) should contain 2 entries",
+            unique
+        );
+    }
+
+    #[test]
+    fn sum_vectors() {
+        let vectors = [Vec3::new(1, 1, 1), Vec3::new(2, 2, 2), Vec3::new(3, 3, 3)];
+        let expected = Vec3::new(6, 6, 6);
+
+        assert_eq!(
+            vectors.iter().copied().sum::<Vec3>(),
+            expected,
+            "Sum of vectors ({:?}) should match expected ({:?})",
+            vectors.iter().copied().sum::<Vec3>(),
+            expected
+        );
+        assert_eq!(
+            vectors.iter().sum::<Vec3>(),
+            expected,
+            "Sum of references ({:?}) should match expected ({:?})",
+            vectors.iter().sum::<Vec3>(),
+            expected
+        );
+    }
+
+    #[test]
+    fn sum_empty() {
+        let vectors: [Vec3; 0] = [];
+
+        assert_eq!(
+            vectors.iter().sum::<Vec3>(),
+            Vec3::ZERO,
+            "Sum of no vectors ({:?}) should be zero",
+            vectors.iter().sum::<Vec3>()
+        );
+    }
+
+    #[test]
+    fn project_onto() {
+        let v = Vec3::new(2, 2, 0);
+        let onto = Vec3::new(1, 0, 0);
+        let expected = Vec3::new(2, 0, 0);
+
+        assert_eq!(
+            v.project_onto(onto),
+            expected,
+            "V.project_onto(Onto) ({:?}) should match expected ({:?})",
+            v.project_onto(onto),
+            expected
+        );
+    }
+
+    #[test]
+    fn reject_from() {
+        let v = Vec3::new(2, 2, 0);
+        let from = Vec3::new(1, 0, 0);
+        let expected = Vec3::new(0, 2, 0);
+
+        assert_eq!(
+            v.reject_from(from),
+            expected,
+            "V.reject_from(From) ({:?}) should match expected ({:?})",
+            v.reject_from(from),
+            expected
+        );
+    }
+
+    #[test]
+    fn angle_between() {
+        use std::f64::consts::{FRAC_PI_2, PI};
+
+        let v = Vec3::new(1, 2, 3);
+        let orthogonal = Vec3::new(2, -1, 0);
+        let opposite = Vec3::new(-2, -4, -6);
+
+        assert_eq!(
+            v.angle_between(orthogonal),
+            FRAC_PI_2,
+            "V.angle_between(Orthogonal) ({}) should match expected ({})",
+            v.angle_between(orthogonal),
+            FRAC_PI_2
+        );
+        assert_eq!(
+            v.angle_between(v),
+            0.0,
+            "V.angle_between(V) ({}) should match expected ({})",
+            v.angle_between(v),
+            0.0
+        );
+        assert_eq!(
+            v.angle_between(opposite),
+            PI,
+            "V.angle_between(Opposite) ({}) should match expected ({})",
+            v.angle_between(opposite),
+            PI
+        );
+    }
+
+    #[test]
+    fn try_from_slice() {
+        let components = [1.0, 2.0, 3.5];
+        let expected = Vec3::new(1, 2, 3.5);
+
+        assert_eq!(
+            Vec3::try_from(&components[..]),
+            Ok(expected),
+            "Vec3::try_from ({:?}) should match expected ({:?})",
+            Vec3::try_from(&components[..]),
+            expected
+        );
+    }
+
+    #[test]
+    fn try_from_slice_wrong_length() {
+        let too_few = [1.0, 2.0];
+        let too_many = [1.0, 2.0, 3.0, 4.0];
+
+        assert_eq!(
+            Vec3::try_from(&too_few[..]),
+            Err(ParseVec3Error::WrongComponentCount(2)),
+            "Vec3::try_from ({:?}) should reject 2 components",
+            Vec3::try_from(&too_few[..])
+        );
+        assert_eq!(
+            Vec3::try_from(&too_many[..]),
+            Err(ParseVec3Error::WrongComponentCount(4)),
+            "Vec3::try_from ({:?}) should reject 4 components",
+            Vec3::try_from(&too_many[..])
+        );
+    }
+
+    #[test]
+    fn max_min_component() {
+        let v = Vec3::new(1, 5, 3);
+
+        assert_eq!(
+            v.max_component(),
+            5.0,
+            "V.max_component() ({}) should match expected ({})",
+            v.max_component(),
+            5.0
+        );
+        assert_eq!(
+            v.min_component(),
+            1.0,
+            "V.min_component() ({}) should match expected ({})",
+            v.min_component(),
+            1.0
+        );
+        assert_eq!(
+            v.max_component_index(),
+            1,
+            "V.max_component_index() ({}) should match expected ({})",
+            v.max_component_index(),
+            1
+        );
+        assert_eq!(
+            v[v.max_component_index()],
+            v.max_component(),
+            "V[V.max_component_index()] ({}) should match V.max_component() ({})",
+            v[v.max_component_index()],
+            v.max_component()
+        );
+    }
+
+    #[test]
+    fn luminance() {
+        let green = Vec3::new(0, 1, 0);
+        let blue = Vec3::new(0, 0, 1);
+
+        assert!(
+            green.luminance() > blue.luminance(),
+            "Green luminance ({}) should be greater than blue luminance ({})",
+            green.luminance(),
+            blue.luminance()
+        );
+        assert!(
+            (Vec3::ONE.luminance() - 1.0).abs() < 1e-12,
+            "Vec3::ONE.luminance() ({}) should be 1.0",
+            Vec3::ONE.luminance()
+        );
+    }
+
+    #[test]
+    fn iterate_components() {
+        let v = Vec3::new(1, 2, 3);
+        let expected = vec![1.0, 2.0, 3.0];
+
+        let components: Vec<f64> = v.into_iter().collect();
+        assert_eq!(
+            components, expected,
+            "V components ({:?}) should match expected ({:?})",
+            components, expected
+        );
+
+        let borrowed_components: Vec<f64> = (&v).into_iter().collect();
+        assert_eq!(
+            borrowed_components, expected,
+            "&V components ({:?}) should match expected ({:?})",
+            borrowed_components, expected
+        );
+    }
+
+    #[test]
+    fn map_vector() {
+        let v = Vec3::new(1, 2, 3);
+        let expected = Vec3::new(2, 4, 6);
+
+        assert_eq!(
+            v.map(|c| c * 2.0),
+            expected,
+            "V.map(|c| c * 2.0) ({:?}) should match expected ({:?})",
+            v.map(|c| c * 2.0),
+            expected
+        );
+    }
+
+    #[test]
+    fn into_rgba_f32() {
+        let color = Vec3::new(0.5, 0.25, 0.75);
+        let expected = [0.5f32, 0.25, 0.75, 1.0];
+        let rgba: [f32; 4] = color.into();
+
+        assert_eq!(
+            rgba, expected,
+            "RGBA ({:?}) should match expected ({:?})",
+            rgba, expected
+        );
+    }
+
+    #[test]
+    fn try_normalized() {
+        let v = Vec3::new(3, 1, 2);
+
+        assert_eq!(
+            v.try_normalized(),
+            Some(v.normalized()),
+            "V.try_normalized() ({:?}) should match V.normalized() ({:?})",
+            v.try_normalized(),
+            v.normalized()
+        );
+        assert_eq!(
+            Vec3::ZERO.try_normalized(),
+            None,
+            "Vec3::ZERO.try_normalized() ({:?}) should be None",
+            Vec3::ZERO.try_normalized()
+        );
+    }
+
+    #[test]
+    fn normalize_in_place() {
+        let mut v = Vec3::new(3, 4, 0);
+        let length = v.normalize();
+
+        assert!(
+            (length - 5.0).abs() < 1e-12,
+            "V.normalize() ({}) should return the original length ({})",
+            length,
+            5.0
+        );
+        assert!(
+            (v.length() - 1.0).abs() < 1e-12,
+            "V ({:?}) should be unit length after normalizing",
+            v
+        );
+    }
+
+    #[test]
+    fn normalize_zero_in_place() {
+        let mut v = Vec3::ZERO;
+        let length = v.normalize();
+
+        assert_eq!(
+            length, 0.0,
+            "Vec3::ZERO.normalize() ({}) should return a length of 0",
+            length
+        );
+        assert_eq!(
+            v,
+            Vec3::ZERO,
+            "Vec3::ZERO ({:?}) should be unchanged after normalizing",
+            v
+        );
+    }
+
+    #[test]
+    fn angle_between_zero() {
+        let v = Vec3::new(1, 2, 3);
+
+        assert!(
+            v.angle_between(Vec3::ZERO).is_nan(),
+            "V.angle_between(Vec3::ZERO) ({}) should be NaN",
+            v.angle_between(Vec3::ZERO)
+        );
+        assert!(
+            Vec3::ZERO.angle_between(v).is_nan(),
+            "Vec3::ZERO.angle_between(V) ({}) should be NaN",
+            Vec3::ZERO.angle_between(v)
+        );
+    }
+
+    #[test]
+    fn project_onto_zero() {
+        let v = Vec3::new(2, 2, 0);
+
+        assert_eq!(
+            v.project_onto(Vec3::ZERO),
+            Vec3::ZERO,
+            "V.project_onto(Vec3::ZERO) ({:?}) should be zero",
+            v.project_onto(Vec3::ZERO)
+        );
+        assert_eq!(
+            v.reject_from(Vec3::ZERO),
+            v,
+            "V.reject_from(Vec3::ZERO) ({:?}) should match V ({:?})",
+            v.reject_from(Vec3::ZERO),
+            v
+        );
+    }
+
+    // A fixed spread of unit directions and normals, standing in for randomly generated
+    // ones since no property testing crate is available to shrink failures
+    fn unit_vectors() -> Vec<Vec3> {
+        vec![
+            Vec3::new(1, 0, 0),
+            Vec3::new(0, -1, 0),
+            Vec3::new(0, 0, 1),
+            Vec3::new(1, 1, 0).normalized(),
+            Vec3::new(-1, 2, 3).normalized(),
+            Vec3::new(0.3, -0.8, 0.1).normalized(),
+            Vec3::new(-5, -1, 2).normalized(),
+        ]
+    }
+
+    #[test]
+    fn reflect_preserves_length() {
+        for v in unit_vectors().iter() {
+            for n in unit_vectors().iter() {
+                let reflected = v.reflect(*n);
+
+                assert!(
+                    (reflected.length() - v.length()).abs() < 1e-10,
+                    "Reflecting {:?} about {:?} ({:?}) should preserve its length",
+                    v,
+                    n,
+                    reflected
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn reflect_twice_is_identity() {
+        for v in unit_vectors().iter() {
+            for n in unit_vectors().iter() {
+                let twice = v.reflect(*n).reflect(*n);
+
+                assert!(
+                    (twice - *v).length() < 1e-10,
+                    "Reflecting {:?} about {:?} twice ({:?}) should give it back",
+                    v,
+                    n,
+                    twice
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn refract_matching_index() {
+        for v in unit_vectors().iter() {
+            for n in unit_vectors().iter() {
+                // Refraction expects the normal to face the incoming vector
+                let n = if v.dot(*n) > 0.0 { *n * -1 } else { *n };
+                let refracted = v.refract(n, 1.0);
+
+                // The parallel part is found with a square root, which turns rounding error
+                // of around 1e-16 into around 1e-8 for vectors grazing the surface
+                assert!(
+                    (refracted - *v).length() < 1e-6,
+                    "Refracting {:?} through {:?} with matching indices ({:?}) should not bend it",
+                    v,
+                    n,
+                    refracted
+                );
+            }
+        }
+    }
 }